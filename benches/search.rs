@@ -0,0 +1,92 @@
+use std::fs::{remove_file, write, File};
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use memmap2::Mmap;
+
+use okolib::search::{count_breakdown, search_bytes, search_patterns};
+
+/// Number of lines of generated log
+const LINES: usize = 100_000;
+
+/// Generate log with rare errors
+fn generate_log() -> PathBuf {
+    let mut content = String::new();
+    for idx in 0..LINES {
+        let level = if idx % 100 == 0 { "ERROR" } else { "INFO" };
+        content.push_str(&format!(
+            "2024-01-01T00:{:02}:{:02} {} user{}@example.com request id={} took {}ms\n",
+            idx / 60 % 60,
+            idx % 60,
+            level,
+            idx % 977,
+            idx,
+            idx % 1000
+        ));
+    }
+
+    let path = std::env::temp_dir().join(format!("oko-bench-{}.log", std::process::id()));
+    write(&path, content).unwrap();
+    path
+}
+
+fn bench_search(c: &mut Criterion) {
+    let path = generate_log();
+    let size = File::open(&path).unwrap().metadata().unwrap().len();
+
+    for (name, pattern) in [
+        ("sparse", "ERROR"),
+        ("dense", r"id=\d+"),
+        ("anchored", r"^\S+ ERROR .*ms$"),
+    ] {
+        let patterns = vec![pattern.to_string()];
+        let mut group = c.benchmark_group(format!("search_{}", name));
+        group.throughput(Throughput::Bytes(size));
+
+        group.bench_function("buffered", |b| {
+            b.iter(|| {
+                let mut reader = BufReader::new(File::open(&path).unwrap());
+                search_patterns(&mut reader, &patterns, &false, &false).unwrap()
+            })
+        });
+        group.bench_function("mmap", |b| {
+            b.iter(|| {
+                let file = File::open(&path).unwrap();
+                let mmap = unsafe { Mmap::map(&file).unwrap() };
+                search_bytes(&mmap, &patterns, &false, &false)
+            })
+        });
+        group.finish();
+    }
+
+    remove_file(&path).unwrap();
+}
+
+fn bench_count(c: &mut Criterion) {
+    let path = generate_log();
+    let size = File::open(&path).unwrap().metadata().unwrap().len();
+
+    let mut group = c.benchmark_group("count");
+    group.throughput(Throughput::Bytes(size));
+    for (name, patterns) in [
+        ("pattern", vec![r"id=\d+".to_string()]),
+        (
+            "patterns",
+            vec!["ERROR".to_string(), r"user\d+@".to_string()],
+        ),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut reader = BufReader::new(File::open(&path).unwrap());
+                count_breakdown(&mut reader, &patterns, &false, &false, &false).unwrap()
+            })
+        });
+    }
+    group.finish();
+
+    remove_file(&path).unwrap();
+}
+
+criterion_group!(benches, bench_search, bench_count);
+criterion_main!(benches);
@@ -0,0 +1,80 @@
+use std::io::{Read, Result, Seek};
+use std::path::Path;
+
+use tar::EntryType;
+use zip::ZipArchive;
+
+use crate::compression::Compression;
+
+/// Separator of archive path and member path, ie `bundle.zip!/logs/app.log`
+pub const MEMBER_SEPARATOR: &str = "!/";
+
+/// Archive of input files
+#[derive(Clone, Debug, PartialEq)]
+pub enum Archive {
+    // Tar, optionally compressed
+    Tar(Option<Compression>),
+    Zip,
+}
+
+impl Archive {
+    /// Get archive from file extension, ie `.zip`, `.tar` or `.tar.gz`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        let archive = if name.ends_with(".zip") {
+            Archive::Zip
+        } else if name.ends_with(".tar") {
+            Archive::Tar(None)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Archive::Tar(Some(Compression::Gzip))
+        } else if name.ends_with(".tar.bz2") || name.ends_with(".tbz2") {
+            Archive::Tar(Some(Compression::Bzip2))
+        } else if name.ends_with(".tar.xz") || name.ends_with(".txz") {
+            Archive::Tar(Some(Compression::Xz))
+        } else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+            Archive::Tar(Some(Compression::Zstd))
+        } else {
+            return None;
+        };
+
+        Some(archive)
+    }
+
+    /// Read every file member of the archive with its path
+    pub fn read_members<'a, R, F>(&self, reader: R, mut callback: F) -> Result<()>
+    where
+        R: Read + Seek + 'a,
+        F: FnMut(&str, &mut dyn Read) -> Result<()>,
+    {
+        match self {
+            Archive::Tar(compression) => {
+                let reader: Box<dyn Read + 'a> = match compression {
+                    Some(compression) => compression.decompress(reader)?,
+                    None => Box::new(reader),
+                };
+                let mut archive = tar::Archive::new(reader);
+                for entry in archive.entries()? {
+                    let mut entry = entry?;
+                    if entry.header().entry_type() != EntryType::Regular {
+                        continue;
+                    }
+                    let path = entry.path()?.to_string_lossy().to_string();
+                    callback(&path, &mut entry)?;
+                }
+            }
+            Archive::Zip => {
+                let mut archive = ZipArchive::new(reader)?;
+                for idx in 0..archive.len() {
+                    let mut file = archive.by_index(idx)?;
+                    if !file.is_file() {
+                        continue;
+                    }
+                    let path = file.name().to_string();
+                    callback(&path, &mut file)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
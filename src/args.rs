@@ -1,9 +1,13 @@
 use std::path::PathBuf;
 
 use clap::ArgAction::SetTrue;
-use clap::{command, Parser, Subcommand};
+use clap::{Parser, Subcommand};
 
+use okolib::binary::BinaryMode;
+use okolib::output_color::OutputColor;
 use okolib::output_format::OutputFormat;
+use okolib::redact::{Detector, Mask};
+use okolib::transform::Operation;
 
 #[derive(Parser)]
 #[clap(
@@ -20,16 +24,89 @@ pub struct Cli {
     // If command is not used, use these top level arguments
     #[arg(help = "Pattern", default_value = "")]
     pub pattern: String,
+    #[arg(
+        short = 'e',
+        long = "regexp",
+        help = "Additional pattern, can be repeated"
+    )]
+    pub patterns: Vec<String>,
     #[arg(short = 'c', long, help = "Ignore case", action = SetTrue)]
     pub ignore_case: bool,
-    #[arg(short = 'i', long, help = "Input file path")]
-    pub input_path: Option<PathBuf>,
+    #[arg(long, help = "Report matches starting at every position, even overlapping", action = SetTrue)]
+    pub overlapping: bool,
+    #[arg(long, help = "Match across lines, dot matches new line", action = SetTrue)]
+    pub multiline: bool,
+    #[arg(
+        long,
+        value_name = "REGEX",
+        help = "Search records starting at lines matching the pattern instead of lines",
+        conflicts_with_all = ["multiline", "record_separator"]
+    )]
+    pub record_start: Option<String>,
+    #[arg(
+        long,
+        value_name = "SEPARATOR",
+        help = "Search records separated by `blank` lines, `nul` or the text instead of lines",
+        conflicts_with = "multiline"
+    )]
+    pub record_separator: Option<String>,
+    #[arg(
+        value_enum,
+        long,
+        help = "Binary files handling [default: report, text with `nul` records]"
+    )]
+    pub binary: Option<BinaryMode>,
+    #[arg(
+        short = 'i',
+        long,
+        help = "Input file path, can be repeated [default: stdin]"
+    )]
+    pub input_path: Vec<PathBuf>,
+    #[arg(
+        long,
+        help = "Input encoding, ie utf-16le or latin1 [default: detected from BOM or utf-8]"
+    )]
+    pub encoding: Option<String>,
+    #[arg(
+        long,
+        help = "Decompress gzip, bzip2, xz or zstd inputs detected by magic bytes, ie stdin [default: by file extension]",
+        action = SetTrue
+    )]
+    pub decompress: bool,
+    #[arg(
+        long = "glob",
+        value_name = "GLOB",
+        help = "Search only archive members matching the glob, ie `logs/*.log`, can be repeated"
+    )]
+    pub globs: Vec<String>,
+    #[arg(long, help = "Never memory-map large input files", action = SetTrue)]
+    pub no_mmap: bool,
+    #[arg(
+        long,
+        help = "Keep reading input files as they grow, like `tail -f`, plain output only",
+        requires = "input_path",
+        conflicts_with_all = ["multiline", "record_start", "record_separator", "decompress", "encoding"],
+        action = SetTrue
+    )]
+    pub follow: bool,
+    #[arg(long, help = "Follow only content appended after start", requires = "follow", action = SetTrue)]
+    pub from_end: bool,
     #[arg(short = 'o', long, help = "Output file path [default: console]")]
     pub output_path: Option<PathBuf>,
     #[arg(value_enum, short = 'f', long, help = "Output format", default_value_t = OutputFormat::Plain)]
     pub output_format: OutputFormat,
+    #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+    pub color: OutputColor,
     #[arg(short = 'n', long, help = "Output line number?", action = SetTrue)]
     pub output_line_number: bool,
+    #[arg(long, help = "Output only matches, one per line", action = SetTrue)]
+    pub only_matching: bool,
+    #[arg(
+        long,
+        value_name = "GROUP",
+        help = "Output only capture group (index or name), one per line"
+    )]
+    pub extract: Option<String>,
 
     // Control logging
     #[arg(short = 'l', long, help = "Log level", default_value = "warn")]
@@ -42,31 +119,337 @@ pub enum Command {
     Search {
         #[arg(help = "Pattern")]
         pattern: String,
+        #[arg(
+            short = 'e',
+            long = "regexp",
+            help = "Additional pattern, can be repeated"
+        )]
+        patterns: Vec<String>,
         #[arg(short = 'c', long, help = "Ignore case", action = SetTrue)]
         ignore_case: bool,
-        #[arg(short = 'i', long, help = "Input file path")]
-        input_path: Option<PathBuf>,
+        #[arg(long, help = "Report matches starting at every position, even overlapping", action = SetTrue)]
+        overlapping: bool,
+        #[arg(long, help = "Match across lines, dot matches new line", action = SetTrue)]
+        multiline: bool,
+        #[arg(
+            long,
+            value_name = "REGEX",
+            help = "Search records starting at lines matching the pattern instead of lines",
+            conflicts_with_all = ["multiline", "record_separator"]
+        )]
+        record_start: Option<String>,
+        #[arg(
+            long,
+            value_name = "SEPARATOR",
+            help = "Search records separated by `blank` lines, `nul` or the text instead of lines",
+            conflicts_with = "multiline"
+        )]
+        record_separator: Option<String>,
+        #[arg(
+            value_enum,
+            long,
+            help = "Binary files handling [default: report, text with `nul` records]"
+        )]
+        binary: Option<BinaryMode>,
+        #[arg(
+            short = 'i',
+            long,
+            help = "Input file path, can be repeated [default: stdin]"
+        )]
+        input_path: Vec<PathBuf>,
+        #[arg(
+            long,
+            help = "Input encoding, ie utf-16le or latin1 [default: detected from BOM or utf-8]"
+        )]
+        encoding: Option<String>,
+        #[arg(
+            long,
+            help = "Decompress gzip, bzip2, xz or zstd inputs detected by magic bytes, ie stdin [default: by file extension]",
+            action = SetTrue
+        )]
+        decompress: bool,
+        #[arg(
+            long = "glob",
+            value_name = "GLOB",
+            help = "Search only archive members matching the glob, ie `logs/*.log`, can be repeated"
+        )]
+        globs: Vec<String>,
+        #[arg(long, help = "Never memory-map large input files", action = SetTrue)]
+        no_mmap: bool,
+        #[arg(
+            long,
+            help = "Keep reading input files as they grow, like `tail -f`, plain output only",
+            requires = "input_path",
+            conflicts_with_all = ["multiline", "record_start", "record_separator", "decompress", "encoding"],
+            action = SetTrue
+        )]
+        follow: bool,
+        #[arg(long, help = "Follow only content appended after start", requires = "follow", action = SetTrue)]
+        from_end: bool,
         #[arg(short = 'o', long, help = "Output file path [default: console]")]
         output_path: Option<PathBuf>,
         #[arg(value_enum, short = 'f', long, help = "Output format", default_value_t = OutputFormat::Plain)]
         output_format: OutputFormat,
+        #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+        color: OutputColor,
         #[arg(short = 'n', long, help = "Output line number?", action = SetTrue)]
         output_line_number: bool,
+        #[arg(long, help = "Output only matches, one per line", action = SetTrue)]
+        only_matching: bool,
+        #[arg(
+            long,
+            value_name = "GROUP",
+            help = "Output only capture group (index or name), one per line"
+        )]
+        extract: Option<String>,
     },
 
     #[clap(alias = "--count")]
     Count {
         #[arg(help = "Pattern")]
         pattern: String,
+        #[arg(
+            short = 'e',
+            long = "regexp",
+            help = "Additional pattern, can be repeated"
+        )]
+        patterns: Vec<String>,
         #[arg(short = 'c', long, help = "Ignore case", action = SetTrue)]
         ignore_case: bool,
-        #[arg(short = 'i', long, help = "Input file path")]
-        input_path: Option<PathBuf>,
+        #[arg(long, help = "Report matches starting at every position, even overlapping", action = SetTrue)]
+        overlapping: bool,
+        #[arg(
+            long,
+            value_name = "REGEX",
+            help = "Count in records starting at lines matching the pattern instead of lines",
+            conflicts_with = "record_separator"
+        )]
+        record_start: Option<String>,
+        #[arg(
+            long,
+            value_name = "SEPARATOR",
+            help = "Count in records separated by `blank` lines, `nul` or the text instead of lines"
+        )]
+        record_separator: Option<String>,
+        #[arg(
+            value_enum,
+            long,
+            help = "Binary files handling [default: report, text with `nul` records]"
+        )]
+        binary: Option<BinaryMode>,
+        #[arg(
+            short = 'i',
+            long,
+            help = "Input file path, can be repeated [default: stdin]"
+        )]
+        input_path: Vec<PathBuf>,
+        #[arg(
+            long,
+            help = "Input encoding, ie utf-16le or latin1 [default: detected from BOM or utf-8]"
+        )]
+        encoding: Option<String>,
+        #[arg(
+            long,
+            help = "Decompress gzip, bzip2, xz or zstd inputs detected by magic bytes, ie stdin [default: by file extension]",
+            action = SetTrue
+        )]
+        decompress: bool,
+        #[arg(
+            long = "glob",
+            value_name = "GLOB",
+            help = "Search only archive members matching the glob, ie `logs/*.log`, can be repeated"
+        )]
+        globs: Vec<String>,
+        #[arg(long, help = "Never memory-map large input files", action = SetTrue)]
+        no_mmap: bool,
         #[arg(short = 'o', long, help = "Output file path [default: console]")]
         output_path: Option<PathBuf>,
         #[arg(value_enum, short = 'f', long, help = "Output format", default_value_t = OutputFormat::Plain)]
         output_format: OutputFormat,
+        #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+        color: OutputColor,
         #[arg(short = 'n', long, help = "Output line number?", action = SetTrue)]
         output_line_number: bool,
+        #[arg(long, help = "Count distinct matches, most common first", action = SetTrue)]
+        frequency: bool,
+        #[arg(
+            long,
+            value_name = "GROUP",
+            help = "Count distinct values of capture group (index or name)",
+            requires = "frequency"
+        )]
+        group: Option<String>,
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Output only N most common values",
+            requires = "frequency"
+        )]
+        top: Option<usize>,
     },
+
+    #[clap(alias = "--replace")]
+    Replace {
+        #[arg(help = "Pattern")]
+        pattern: String,
+        #[arg(help = "Replacement, use $1 or ${name} to reference capture groups")]
+        replacement: String,
+        #[arg(short = 'c', long, help = "Ignore case", action = SetTrue)]
+        ignore_case: bool,
+        #[arg(
+            short = 'i',
+            long,
+            help = "Input file path, can be repeated [default: stdin]"
+        )]
+        input_path: Vec<PathBuf>,
+        #[arg(
+            long,
+            help = "Input encoding, ie utf-16le or latin1 [default: detected from BOM or utf-8]",
+            conflicts_with_all = ["in_place", "dry_run"]
+        )]
+        encoding: Option<String>,
+        #[arg(
+            short = 'o',
+            long,
+            help = "Output file path [default: console]",
+            conflicts_with = "in_place"
+        )]
+        output_path: Option<PathBuf>,
+        #[arg(
+            value_enum,
+            short = 'f',
+            long,
+            help = "Output format",
+            default_value_t = OutputFormat::Plain,
+            conflicts_with = "in_place"
+        )]
+        output_format: OutputFormat,
+        #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+        color: OutputColor,
+        #[arg(long, help = "Edit input files in place", action = SetTrue, requires = "input_path")]
+        in_place: bool,
+        #[arg(
+            long,
+            help = "Backup suffix of edited files, ie .bak",
+            requires = "in_place"
+        )]
+        backup: Option<String>,
+        #[arg(
+            long,
+            help = "Print unified diff of changes instead of writing files",
+            action = SetTrue,
+            requires = "input_path"
+        )]
+        dry_run: bool,
+    },
+    #[clap(alias = "--transform")]
+    Transform {
+        #[arg(help = "Pattern of text to transform [default: whole lines]")]
+        pattern: Option<String>,
+        #[arg(
+            long = "op",
+            required = true,
+            help = "Operation, can be repeated: upper, lower, title, trim, slugify, nfc, nfkc, reverse, pad:<width> or pad-left:<width>"
+        )]
+        operations: Vec<Operation>,
+        #[arg(short = 'c', long, help = "Ignore case", action = SetTrue)]
+        ignore_case: bool,
+        #[arg(
+            short = 'i',
+            long,
+            help = "Input file path, can be repeated [default: stdin]"
+        )]
+        input_path: Vec<PathBuf>,
+        #[arg(
+            long,
+            help = "Input encoding, ie utf-16le or latin1 [default: detected from BOM or utf-8]"
+        )]
+        encoding: Option<String>,
+        #[arg(short = 'o', long, help = "Output file path [default: console]")]
+        output_path: Option<PathBuf>,
+        #[arg(value_enum, short = 'f', long, help = "Output format", default_value_t = OutputFormat::Plain)]
+        output_format: OutputFormat,
+        #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+        color: OutputColor,
+    },
+    #[clap(alias = "--redact")]
+    Redact {
+        #[arg(
+            short = 'e',
+            long = "regexp",
+            help = "Additional pattern to redact, can be repeated"
+        )]
+        patterns: Vec<String>,
+        #[arg(
+            value_enum,
+            short = 'd',
+            long = "detect",
+            help = "Built-in detector, can be repeated [default: all]"
+        )]
+        detectors: Vec<Detector>,
+        #[arg(long, help = "Use only additional patterns", action = SetTrue, requires = "patterns")]
+        no_detectors: bool,
+        #[arg(value_enum, short = 'm', long, help = "Mask", default_value_t = Mask::Fixed)]
+        mask: Mask,
+        #[arg(long, help = "Text of fixed mask", default_value = "[REDACTED]")]
+        mask_text: String,
+        #[arg(short = 'c', long, help = "Ignore case of additional patterns", action = SetTrue)]
+        ignore_case: bool,
+        #[arg(
+            short = 'i',
+            long,
+            help = "Input file path, can be repeated [default: stdin]"
+        )]
+        input_path: Vec<PathBuf>,
+        #[arg(
+            long,
+            help = "Input encoding, ie utf-16le or latin1 [default: detected from BOM or utf-8]"
+        )]
+        encoding: Option<String>,
+        #[arg(short = 'o', long, help = "Output file path [default: console]")]
+        output_path: Option<PathBuf>,
+        #[arg(value_enum, short = 'f', long, help = "Output format", default_value_t = OutputFormat::Plain)]
+        output_format: OutputFormat,
+        #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+        color: OutputColor,
+    },
+    /// Search or count again whenever input files change
+    Watch {
+        #[arg(help = "Pattern")]
+        pattern: String,
+        #[arg(
+            short = 'e',
+            long = "regexp",
+            help = "Additional pattern, can be repeated"
+        )]
+        patterns: Vec<String>,
+        #[arg(short = 'c', long, help = "Ignore case", action = SetTrue)]
+        ignore_case: bool,
+        #[arg(long, help = "Report matches starting at every position, even overlapping", action = SetTrue)]
+        overlapping: bool,
+        #[arg(
+            short = 'i',
+            long,
+            help = "Input file path, can be repeated",
+            required = true
+        )]
+        input_path: Vec<PathBuf>,
+        #[arg(long, help = "Count matches, showing changes since previous run", action = SetTrue)]
+        count: bool,
+        #[arg(value_enum, long, help = "Color output", default_value_t = OutputColor::Auto)]
+        color: OutputColor,
+        #[arg(short = 'n', long, help = "Output line number?", action = SetTrue)]
+        output_line_number: bool,
+    },
+    /// Named patterns
+    Patterns {
+        #[command(subcommand)]
+        command: PatternsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PatternsCommand {
+    /// List built-in patterns and user aliases
+    List,
 }
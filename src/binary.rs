@@ -0,0 +1,72 @@
+use std::io::{BufRead, Read, Result};
+
+use clap::ValueEnum;
+
+/// Size of the first block checked for binary content
+pub const BLOCK_SIZE: usize = 8 * 1024;
+
+/// Handling of binary inputs
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
+pub enum BinaryMode {
+    // Skip binary inputs
+    Skip,
+    // Search binary inputs as text
+    Text,
+    // Report only whether binary inputs match
+    Report,
+}
+
+/// Check whether content looks binary, ie contains NUL bytes in the first block
+pub fn is_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BLOCK_SIZE)].contains(&0)
+}
+
+/// Reader decoding UTF-8 lossily, ie invalid sequences are replaced with `U+FFFD`
+///
+/// Content is decoded line by line, so sequences are never split.
+pub struct LossyReader<R: BufRead> {
+    inner: R,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: BufRead> LossyReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buffer: vec![],
+            pos: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for LossyReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let available = self.fill_buf()?;
+        let size = available.len().min(buf.len());
+        buf[..size].copy_from_slice(&available[..size]);
+        self.consume(size);
+        Ok(size)
+    }
+}
+
+impl<R: BufRead> BufRead for LossyReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.pos >= self.buffer.len() {
+            // Buffer is reused, only invalid lines are allocated when decoded
+            self.buffer.clear();
+            self.inner.read_until(b'\n', &mut self.buffer)?;
+            if std::str::from_utf8(&self.buffer).is_err() {
+                self.buffer = String::from_utf8_lossy(&self.buffer)
+                    .into_owned()
+                    .into_bytes();
+            }
+            self.pos = 0;
+        }
+        Ok(&self.buffer[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.buffer.len());
+    }
+}
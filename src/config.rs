@@ -1,14 +1,44 @@
-use std::io::Result;
+use std::collections::BTreeMap;
+use std::io::{Error, Result};
 
 use confy::load;
 use log::info;
-use owo_colors::AnsiColors;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Serialize, Deserialize)]
+use okolib::output_style::TextStyle;
+
+/// Configuration
+///
+/// Colors are styles like `green`, `bold #ff8800` or `underline 208 on black`.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     content_color: String,
     match_color: String,
+    line_number_color: String,
+    path_color: String,
+    separator_color: String,
+    // Colors of additional patterns and capture groups
+    match_palette: Vec<String>,
+    // Pattern aliases usable as `@name`, ie `ticket = "[A-Z]+-\\d+"`
+    patterns: BTreeMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            content_color: "".to_string(),
+            match_color: "".to_string(),
+            line_number_color: "".to_string(),
+            path_color: "".to_string(),
+            separator_color: "".to_string(),
+            match_palette: ["yellow", "cyan", "magenta", "blue", "red"]
+                .iter()
+                .map(|color| color.to_string())
+                .collect(),
+            patterns: BTreeMap::new(),
+        }
+    }
 }
 
 impl Config {
@@ -16,24 +46,59 @@ impl Config {
         Self {
             content_color,
             match_color,
+            ..Default::default()
         }
     }
 
-    pub fn get_content_color(&self) -> Result<Option<AnsiColors>> {
-        let mut color: Option<AnsiColors> = None;
-        if !self.content_color.eq("") {
-            color = Some(AnsiColors::from(self.content_color.as_str()));
-        }
-        Ok(color)
+    pub fn get_content_color(&self) -> Result<Option<TextStyle>> {
+        get_color(&self.content_color)
     }
 
-    pub fn get_match_color(&self) -> Result<Option<AnsiColors>> {
-        let mut color: Option<AnsiColors> = None;
-        if !self.match_color.eq("") {
-            color = Some(AnsiColors::from(self.match_color.as_str()));
-        }
-        Ok(color)
+    pub fn get_match_color(&self) -> Result<Option<TextStyle>> {
+        get_color(&self.match_color)
+    }
+
+    pub fn get_line_number_color(&self) -> Result<Option<TextStyle>> {
+        get_color(&self.line_number_color)
+    }
+
+    pub fn get_path_color(&self) -> Result<Option<TextStyle>> {
+        get_color(&self.path_color)
     }
+
+    pub fn get_separator_color(&self) -> Result<Option<TextStyle>> {
+        get_color(&self.separator_color)
+    }
+
+    pub fn get_match_palette(&self) -> Result<Vec<TextStyle>> {
+        self.match_palette
+            .iter()
+            .map(|color| color.parse())
+            .collect()
+    }
+
+    pub fn get_patterns(&self) -> &BTreeMap<String, String> {
+        &self.patterns
+    }
+
+    /// Check that all colors are valid styles
+    pub fn validate(&self) -> Result<()> {
+        self.get_content_color()?;
+        self.get_match_color()?;
+        self.get_line_number_color()?;
+        self.get_path_color()?;
+        self.get_separator_color()?;
+        self.get_match_palette()?;
+        Ok(())
+    }
+}
+
+fn get_color(color: &str) -> Result<Option<TextStyle>> {
+    let mut style: Option<TextStyle> = None;
+    if !color.is_empty() {
+        style = Some(color.parse()?);
+    }
+    Ok(style)
 }
 
 /// Get configuration
@@ -57,5 +122,13 @@ pub fn get_config(config_name: &str) -> Result<Config> {
         })
         .unwrap();
 
+    // Invalid colors are reported once loaded, rather than when output is written
+    cfg.validate().map_err(|error| {
+        Error::new(
+            error.kind(),
+            format!("Invalid configuration {}: {}", config_name, error),
+        )
+    })?;
+
     Ok(cfg)
 }
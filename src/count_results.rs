@@ -0,0 +1,183 @@
+use serde::Serialize;
+
+/// Count results with breakdown per input file, pattern and line
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CountResults {
+    pub pattern: String,
+    // Number of occurrences
+    pub total: usize,
+    // Number of lines with occurrences
+    pub lines: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<PatternCount>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileCount>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub line_counts: Vec<LineCount>,
+    // Whether records are counted instead of lines
+    #[serde(skip)]
+    pub records: bool,
+}
+
+impl CountResults {
+    pub fn new(pattern: String) -> Self {
+        Self {
+            pattern,
+            total: 0,
+            lines: 0,
+            patterns: vec![],
+            files: vec![],
+            line_counts: vec![],
+            records: false,
+        }
+    }
+
+    /// Add counts of the line
+    ///
+    /// Pattern counts are indexed by pattern and kept only for multiple patterns.
+    pub fn add_line(&mut self, line: usize, pattern_counts: &[usize], per_line: bool) {
+        let total: usize = pattern_counts.iter().sum();
+        if total == 0 {
+            return;
+        }
+
+        self.total += total;
+        self.lines += 1;
+        for (idx, count) in pattern_counts.iter().enumerate() {
+            if let Some(pattern) = self.patterns.get_mut(idx) {
+                pattern.total += count;
+            }
+        }
+        if per_line {
+            self.line_counts.push(LineCount::new(line, total));
+        }
+    }
+
+    /// Add counts of an input file, tagged with its path
+    pub fn add_file(&mut self, path: String, results: CountResults) {
+        self.total += results.total;
+        self.lines += results.lines;
+        self.records |= results.records;
+        for (pattern, other) in self.patterns.iter_mut().zip(&results.patterns) {
+            pattern.total += other.total;
+        }
+        self.files
+            .push(FileCount::new(path.clone(), results.total, results.lines));
+        for mut line_count in results.line_counts {
+            line_count.path = Some(path.clone());
+            self.line_counts.push(line_count);
+        }
+    }
+
+    /// Get unit of counted lines, ie `line` or `record`
+    pub fn get_unit(&self) -> &'static str {
+        if self.records {
+            "record"
+        } else {
+            "line"
+        }
+    }
+
+    /// Check whether there is any breakdown besides totals
+    pub fn has_breakdown(&self) -> bool {
+        !self.patterns.is_empty() || !self.files.is_empty() || !self.line_counts.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PatternCount {
+    pub pattern: String,
+    pub total: usize,
+}
+
+impl PatternCount {
+    pub fn new(pattern: String, total: usize) -> Self {
+        Self { pattern, total }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FileCount {
+    pub path: String,
+    pub total: usize,
+    pub lines: usize,
+}
+
+impl FileCount {
+    pub fn new(path: String, total: usize, lines: usize) -> Self {
+        Self { path, total, lines }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LineCount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub line: usize,
+    pub total: usize,
+}
+
+impl LineCount {
+    pub fn new(line: usize, total: usize) -> Self {
+        Self {
+            path: None,
+            line,
+            total,
+        }
+    }
+}
+
+/// Change of counts between two runs, ie in watch mode
+#[derive(Debug, PartialEq)]
+pub struct CountDelta {
+    pub total: i64,
+    // Changed files, including files without matches anymore
+    pub files: Vec<(String, i64)>,
+    // Every pattern, in order
+    pub patterns: Vec<(String, i64)>,
+}
+
+impl CountDelta {
+    pub fn new(previous: &CountResults, current: &CountResults) -> Self {
+        let get_delta = |previous: usize, current: usize| current as i64 - previous as i64;
+        let get_file_total = |results: &CountResults, path: &str| {
+            results
+                .files
+                .iter()
+                .find(|file| file.path == path)
+                .map_or(0, |file| file.total)
+        };
+
+        let mut files = vec![];
+        for file in &current.files {
+            files.push((
+                file.path.clone(),
+                get_delta(get_file_total(previous, &file.path), file.total),
+            ));
+        }
+        for file in &previous.files {
+            if !current.files.iter().any(|other| other.path == file.path) {
+                files.push((file.path.clone(), get_delta(file.total, 0)));
+            }
+        }
+        files.retain(|(_, delta)| *delta != 0);
+
+        let patterns = current
+            .patterns
+            .iter()
+            .zip(&previous.patterns)
+            .map(|(pattern, other)| {
+                (
+                    pattern.pattern.clone(),
+                    get_delta(other.total, pattern.total),
+                )
+            })
+            .collect();
+
+        Self {
+            total: get_delta(previous.total, current.total),
+            files,
+            patterns,
+        }
+    }
+}
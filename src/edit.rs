@@ -0,0 +1,62 @@
+use std::ffi::OsString;
+use std::fs::{copy, metadata, remove_file, rename, set_permissions, File};
+use std::io::{Result, Write};
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
+
+/// Write content to the file atomically
+///
+/// Content is written to a temporary file next to the target, which then replaces the
+/// target using rename, so the target is never left truncated. When a backup suffix is
+/// given the original file is copied to `<path><suffix>` first.
+pub fn write_file(path: &Path, content: &[u8], backup_suffix: Option<&str>) -> Result<()> {
+    if let Some(suffix) = backup_suffix {
+        copy(path, append_to_path(path, suffix))?;
+    }
+
+    let temp_path = get_temp_path(path);
+    let written = write_temp_file(path, &temp_path, content);
+    if written.is_err() {
+        let _ = remove_file(&temp_path);
+        return written;
+    }
+
+    rename(&temp_path, path)
+}
+
+/// Get unified diff between original and changed content of the file
+///
+/// Returns empty string when content is unchanged.
+pub fn get_diff(path: &Path, original: &str, changed: &str) -> String {
+    if original == changed {
+        return String::new();
+    }
+
+    let display_path = path.display().to_string();
+    TextDiff::from_lines(original, changed)
+        .unified_diff()
+        .context_radius(3)
+        .header(&display_path, &display_path)
+        .to_string()
+}
+
+fn write_temp_file(path: &Path, temp_path: &Path, content: &[u8]) -> Result<()> {
+    let mut file = File::create(temp_path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+
+    // Keep permissions of the original file
+    set_permissions(temp_path, metadata(path)?.permissions())
+}
+
+fn get_temp_path(path: &Path) -> PathBuf {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.oko-{}.tmp", file_name, std::process::id()))
+}
+
+fn append_to_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut new_path: OsString = path.as_os_str().to_owned();
+    new_path.push(suffix);
+    PathBuf::from(new_path)
+}
@@ -0,0 +1,104 @@
+use std::borrow::Cow;
+use std::io::{Error, ErrorKind, Read, Result};
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE};
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+
+/// Byte order marks
+pub const UTF8_BOM: &[u8] = &[0xef, 0xbb, 0xbf];
+pub const UTF16LE_BOM: &[u8] = &[0xff, 0xfe];
+pub const UTF16BE_BOM: &[u8] = &[0xfe, 0xff];
+
+/// Get encoding by its label, ie `utf-16le`, `latin1` or `windows-1252`
+pub fn get_encoding(label: &str) -> Result<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes()).ok_or(Error::new(
+        ErrorKind::InvalidInput,
+        format!("Unknown encoding {}", label),
+    ))
+}
+
+/// Get reader transcoding content into UTF-8
+///
+/// Encoding is detected from BOM, which is stripped. Without BOM content is decoded
+/// using the encoding, or passed through as UTF-8 if there is none.
+pub fn decode<R: Read>(
+    reader: R,
+    encoding: Option<&'static Encoding>,
+) -> DecodeReaderBytes<R, Vec<u8>> {
+    DecodeReaderBytesBuilder::new()
+        .encoding(encoding)
+        .bom_override(true)
+        .strip_bom(true)
+        .utf8_passthru(true)
+        .build(reader)
+}
+
+/// Encoding of file edited in place, detected from BOM
+///
+/// Files are edited as UTF-8 without BOM, then encoded back with the same BOM.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16LeBom,
+    Utf16BeBom,
+}
+
+impl FileEncoding {
+    pub fn detect(content: &[u8]) -> Self {
+        if content.starts_with(UTF8_BOM) {
+            FileEncoding::Utf8Bom
+        } else if content.starts_with(UTF16LE_BOM) {
+            FileEncoding::Utf16LeBom
+        } else if content.starts_with(UTF16BE_BOM) {
+            FileEncoding::Utf16BeBom
+        } else {
+            FileEncoding::Utf8
+        }
+    }
+
+    /// Get content to edit, ie without BOM and as UTF-8 if it is UTF-16
+    ///
+    /// Invalid UTF-8 is kept as it is, malformed UTF-16 can't be edited.
+    pub fn decode<'a>(&self, content: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+        match self {
+            FileEncoding::Utf8 => Some(Cow::Borrowed(content)),
+            FileEncoding::Utf8Bom => Some(Cow::Borrowed(&content[UTF8_BOM.len()..])),
+            FileEncoding::Utf16LeBom | FileEncoding::Utf16BeBom => {
+                let encoding = if *self == FileEncoding::Utf16LeBom {
+                    UTF_16LE
+                } else {
+                    UTF_16BE
+                };
+                encoding
+                    .decode_without_bom_handling_and_without_replacement(&content[2..])
+                    .map(|content| Cow::Owned(content.into_owned().into_bytes()))
+            }
+        }
+    }
+
+    /// Encode edited content back, with BOM
+    pub fn encode(&self, content: &[u8]) -> Vec<u8> {
+        match self {
+            FileEncoding::Utf8 => content.to_vec(),
+            FileEncoding::Utf8Bom => [UTF8_BOM, content].concat(),
+            FileEncoding::Utf16LeBom => [
+                UTF16LE_BOM.to_vec(),
+                encode_utf16(content, u16::to_le_bytes),
+            ]
+            .concat(),
+            FileEncoding::Utf16BeBom => [
+                UTF16BE_BOM.to_vec(),
+                encode_utf16(content, u16::to_be_bytes),
+            ]
+            .concat(),
+        }
+    }
+}
+
+fn encode_utf16(content: &[u8], to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    String::from_utf8_lossy(content)
+        .encode_utf16()
+        .flat_map(to_bytes)
+        .collect()
+}
@@ -0,0 +1,151 @@
+use std::fs::{metadata, File, Metadata};
+use std::io::{BufRead, BufReader, Read, Result, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Interval of polling followed files for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Complete lines appended to followed file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chunk {
+    // Number of the first line
+    pub line: usize,
+    pub content: Vec<u8>,
+}
+
+/// Follower of growing file, like `tail -f`
+///
+/// Truncated file is read again from start. When file is rotated, ie renamed and created
+/// again, rest of the old file is read before the new one.
+pub struct Follower {
+    path: PathBuf,
+    file: File,
+    id: Option<(u64, u64)>,
+    // Position after the last read byte
+    position: u64,
+    // Number of complete lines read
+    line: usize,
+    // Incomplete last line
+    partial: Vec<u8>,
+}
+
+impl Follower {
+    /// Follow file from start, or from end skipping existing lines
+    pub fn new(path: &Path, from_end: bool) -> Result<Self> {
+        let file = File::open(path)?;
+        let mut follower = Self {
+            path: path.to_path_buf(),
+            id: get_file_id(&file.metadata()?),
+            file,
+            position: 0,
+            line: 0,
+            partial: vec![],
+        };
+        if from_end {
+            follower.skip_lines()?;
+        }
+
+        Ok(follower)
+    }
+
+    /// Read complete lines appended since last read
+    pub fn read(&mut self) -> Result<Vec<Chunk>> {
+        let mut chunks = vec![];
+
+        if self.file.metadata()?.len() < self.position {
+            // Truncated
+            self.position = 0;
+            self.line = 0;
+            self.partial.clear();
+        }
+        chunks.extend(self.read_lines()?);
+
+        if self.is_rotated() {
+            // Incomplete last line of the old file is complete now
+            if !self.partial.is_empty() {
+                self.partial.push(b'\n');
+                chunks.extend(self.take_lines());
+            }
+
+            self.file = File::open(&self.path)?;
+            self.id = get_file_id(&self.file.metadata()?);
+            self.position = 0;
+            self.line = 0;
+            chunks.extend(self.read_lines()?);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Skip existing complete lines, counting them
+    fn skip_lines(&mut self) -> Result<()> {
+        let mut reader = BufReader::new(&self.file);
+        loop {
+            let buffer = reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+
+            self.line += buffer.iter().filter(|b| **b == b'\n').count();
+            if let Some(idx) = buffer.iter().rposition(|b| *b == b'\n') {
+                // Incomplete last line is read later
+                self.partial.clear();
+                self.partial.extend_from_slice(&buffer[idx + 1..]);
+            } else {
+                self.partial.extend_from_slice(buffer);
+            }
+            let size = buffer.len();
+            reader.consume(size);
+            self.position += size as u64;
+        }
+
+        self.position -= self.partial.len() as u64;
+        self.partial.clear();
+        Ok(())
+    }
+
+    fn read_lines(&mut self) -> Result<Option<Chunk>> {
+        self.file.seek(SeekFrom::Start(self.position))?;
+        let size = self.file.read_to_end(&mut self.partial)?;
+        self.position += size as u64;
+
+        Ok(self.take_lines())
+    }
+
+    fn take_lines(&mut self) -> Option<Chunk> {
+        let end = self.partial.iter().rposition(|b| *b == b'\n')? + 1;
+        let rest = self.partial.split_off(end);
+        let content = std::mem::replace(&mut self.partial, rest);
+
+        let chunk = Chunk {
+            line: self.line + 1,
+            content,
+        };
+        self.line += chunk.content.iter().filter(|b| **b == b'\n').count();
+        Some(chunk)
+    }
+
+    /// Check whether file at the path is another file than the followed one
+    fn is_rotated(&self) -> bool {
+        match metadata(&self.path) {
+            Ok(path_metadata) => get_file_id(&path_metadata) != self.id,
+            // Not created again yet
+            Err(_) => false,
+        }
+    }
+}
+
+/// Get identity of file, ie device and inode
+#[cfg(unix)]
+fn get_file_id(file_metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some((file_metadata.dev(), file_metadata.ino()))
+}
+
+/// Get identity of file, rotation is detected only on unix
+#[cfg(not(unix))]
+fn get_file_id(_file_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::search_results::SearchResults;
+
+/// Frequency of distinct values, most common first
+#[derive(Debug, PartialEq, Serialize)]
+pub struct FrequencyResults {
+    pub pattern: String,
+    // Number of all values, including values cut by top
+    pub total: usize,
+    // Number of distinct values, including values cut by top
+    pub distinct: usize,
+    pub results: Vec<Frequency>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Frequency {
+    pub value: String,
+    pub count: usize,
+}
+
+impl Frequency {
+    pub fn new(value: String, count: usize) -> Self {
+        Self { value, count }
+    }
+}
+
+/// Tally distinct values of search results
+///
+/// Values are extracted matches (see `SearchResults::extract`) or whole matches. Values are
+/// sorted by count and then by value, only top values are kept if requested.
+pub fn frequency(results: &SearchResults, top: Option<usize>) -> FrequencyResults {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut total = 0usize;
+    for result in &results.results {
+        let values: Vec<&str> = if result.extracted.is_empty() {
+            result.matches.iter().map(|m| m.content.as_str()).collect()
+        } else {
+            result.extracted.iter().map(|v| v.as_str()).collect()
+        };
+        for value in values {
+            *counts.entry(value).or_default() += 1;
+            total += 1;
+        }
+    }
+
+    let distinct = counts.len();
+    let mut frequencies: Vec<Frequency> = counts
+        .into_iter()
+        .map(|(value, count)| Frequency::new(value.to_string(), count))
+        .collect();
+    frequencies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    if let Some(top) = top {
+        frequencies.truncate(top);
+    }
+
+    FrequencyResults {
+        pattern: results.pattern.clone(),
+        total,
+        distinct,
+        results: frequencies,
+    }
+}
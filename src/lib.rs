@@ -2,10 +2,27 @@
 //!
 //! Search, Count, Transform and Output
 
+pub mod archive;
+pub mod binary;
+pub mod compression;
+pub mod count_results;
+pub mod edit;
+pub mod encoding;
+pub mod follow;
+pub mod frequency;
 pub mod output;
+pub mod output_color;
+pub mod output_csv;
 pub mod output_destination;
 pub mod output_format;
+pub mod output_html;
+pub mod output_markdown;
 pub mod output_style;
 pub mod output_writer;
+pub mod patterns;
+pub mod records;
+pub mod redact;
 pub mod search;
 pub mod search_results;
+pub mod transform;
+pub mod watch;
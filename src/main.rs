@@ -1,18 +1,46 @@
 #![doc = include_str!("../README.md")]
 
-use std::io::Result;
+use std::fmt::Display;
+use std::fs::read;
+use std::io::{stdout, BufRead, IsTerminal, Result};
+use std::path::PathBuf;
+use std::thread::sleep;
 
-use clap::Parser;
-use log::info;
+use anyhow::Context;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use encoding_rs::Encoding;
+use log::{info, warn};
+use regex::Regex;
 
-use crate::args::{Cli, Command};
+use crate::args::{Cli, Command, PatternsCommand};
 use crate::config::{get_config, Config};
 use crate::logging::setup_logging;
-use crate::reader::get_reader;
+use crate::reader::{get_reader, read_inputs, Content, Inputs};
 use crate::writer::get_writer;
 
-use okolib::output::{write_count_results, write_search_results};
-use okolib::search::{count, search};
+use okolib::binary::{is_binary, BinaryMode, LossyReader};
+use okolib::compression::Compression;
+use okolib::count_results::{CountDelta, CountResults, PatternCount};
+use okolib::edit::{get_diff, write_file};
+use okolib::encoding::{get_encoding, FileEncoding};
+use okolib::follow::{Follower, POLL_INTERVAL};
+use okolib::frequency::frequency as frequency_of;
+use okolib::output::{
+    write_count_delta, write_count_results, write_frequency_results, write_redact_results,
+    write_replace_results, write_search_results,
+};
+use okolib::output_format::OutputFormat;
+use okolib::patterns::{expand_pattern, BUILTIN_PATTERNS};
+use okolib::records::RecordSeparator;
+use okolib::redact::{Detector, RedactResults, Redactor};
+use okolib::search::{
+    check_group, count_breakdown, count_records, replace, replace_bytes, search_bytes,
+    search_multiline, search_patterns, search_records,
+};
+use okolib::search_results::SearchResults;
+use okolib::transform::{transform_lines, Operation};
+use okolib::watch::Watcher;
 
 mod args;
 mod config;
@@ -20,6 +48,9 @@ mod logging;
 mod reader;
 mod writer;
 
+/// Clear console and move cursor to its start
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
 /// CLI
 ///
 /// Example (Search "here" in content.txt file):
@@ -34,6 +65,74 @@ mod writer;
 /// ```bash
 /// cat examples/content.txt | oko here
 /// ```
+///
+/// Example (Top 5 client IPs in access.log):
+/// ```bash
+/// oko count @ipv4 -i access.log --frequency --top 5
+/// ```
+///
+/// Example (Replace "here" with "there" in content.txt file):
+/// ```bash
+/// oko replace here there -i examples/content.txt
+/// ```
+///
+/// Example (Preview and apply replacement in content.txt file, keeping a backup):
+/// ```bash
+/// oko replace here there -i examples/content.txt --dry-run
+/// oko replace here there -i examples/content.txt --in-place --backup .bak
+/// ```
+///
+/// Example (Redact emails, IPs, tokens and card numbers from log with stable hashes):
+/// ```bash
+/// cat app.log | oko redact --mask hash
+/// ```
+///
+/// Example (Search stack traces spanning lines):
+/// ```bash
+/// oko 'Exception[^\n]*(\n[ \t]+at [^\n]*)+' --multiline -i app.log
+/// ```
+///
+/// Example (Search multi-line log entries starting with a date):
+/// ```bash
+/// oko ERROR --record-start '^\d{4}-\d{2}-\d{2}' -i app.log
+/// ```
+///
+/// Example (Search UTF-16 or Windows-1252 log exports):
+/// ```bash
+/// oko error -i export-utf16.log
+/// oko error -i export.log --encoding windows-1252
+/// ```
+///
+/// Example (Search rotated logs, compressed or not, and compressed stdin):
+/// ```bash
+/// oko error -i app.log -i app.log.1.gz -i app.log.2.zst
+/// cat app.log.3.xz | oko error --decompress
+/// ```
+///
+/// Example (Search logs inside a support bundle, results are tagged as `bundle.zip!/logs/app.log`):
+/// ```bash
+/// oko error -i bundle.zip -i bundle.tar.gz --glob 'logs/*.log'
+/// ```
+///
+/// Example (Follow growing log like `tail -f | grep`, surviving rotation):
+/// ```bash
+/// oko ERROR --follow --from-end -i app.log
+/// ```
+///
+/// Example (Count errors in logs whenever they change, showing changes since previous run):
+/// ```bash
+/// oko watch ERROR -e WARN -i app.log -i worker.log --count
+/// ```
+///
+/// Example (Search email addresses using named pattern, see `oko patterns list`):
+/// ```bash
+/// oko @email -i examples/content.txt
+/// ```
+///
+/// Example (Uppercase names in content.txt file):
+/// ```bash
+/// oko transform 'Ja\w+' --op upper -i examples/content.txt
+/// ```
 fn main() -> Result<()> {
     // Parse arguments
     let args = Cli::parse();
@@ -44,78 +143,463 @@ fn main() -> Result<()> {
     info!("OKO");
 
     // Get configuration
-    let cfg: Config = get_config("local").unwrap();
+    let cfg: Config = get_config("local").unwrap_or_else(|error| exit_with_error(error));
 
     // Execute command or defaults
     match args.command {
         Some(Command::Search {
             pattern,
+            patterns,
             ignore_case,
+            overlapping,
+            multiline,
+            record_start,
+            record_separator,
+            binary,
             input_path,
+            encoding,
+            decompress,
+            globs,
+            no_mmap,
+            follow,
+            from_end,
             output_path,
             output_format,
+            color,
             output_line_number,
+            only_matching,
+            extract,
         }) => {
-            // Get reader, ie content
-            let mut reader = get_reader(input_path).unwrap();
+            let patterns = get_patterns(pattern, patterns, &cfg);
+            check_extract(&patterns, extract.as_deref());
 
-            // Search pattern
-            let results = search(&mut reader, &pattern, &ignore_case).unwrap();
+            if follow {
+                check_follow_format(&output_format);
 
-            // Get writer
-            let mut writer = get_writer(
-                output_path,
-                output_format,
-                output_line_number,
-                cfg.get_content_color().unwrap(),
-                cfg.get_match_color().unwrap(),
+                // Get writer
+                let mut writer =
+                    get_writer(output_path, output_format, color, output_line_number, &cfg)
+                        .unwrap();
+
+                // Output results of appended lines as they come
+                follow_inputs(
+                    input_path,
+                    from_end,
+                    &patterns,
+                    &ignore_case,
+                    &overlapping,
+                    |mut results| {
+                        if only_matching || extract.is_some() {
+                            results.extract(extract.as_deref());
+                        }
+                        write_search_results(results, &mut writer);
+                        writer.flush();
+                    },
+                )
+                .unwrap();
+                return Ok(());
+            }
+
+            // Search patterns in all inputs
+            let mut inputs = Inputs::new(
+                input_path,
+                get_binary_mode(binary, &record_separator),
+                get_encoding_of(encoding),
+                decompress,
+            );
+            inputs.set_globs(&globs);
+            inputs.mmap = !no_mmap;
+            let records = get_record_separator(record_start, record_separator, &cfg);
+            let mut results = search_inputs(
+                inputs,
+                &patterns,
+                &ignore_case,
+                &overlapping,
+                &multiline,
+                &records,
             )
             .unwrap();
+            if only_matching || extract.is_some() {
+                results.extract(extract.as_deref());
+            }
+
+            // Get writer
+            let mut writer =
+                get_writer(output_path, output_format, color, output_line_number, &cfg).unwrap();
 
             // Output results
             write_search_results(results, &mut writer);
         }
         Some(Command::Count {
             pattern,
+            patterns,
             ignore_case,
+            overlapping,
+            record_start,
+            record_separator,
+            binary,
             input_path,
+            encoding,
+            decompress,
+            globs,
+            no_mmap,
             output_path,
             output_format,
+            color,
             output_line_number,
+            frequency,
+            group,
+            top,
         }) => {
-            // Get reader, ie content
-            let mut reader = get_reader(input_path).unwrap();
+            let patterns = get_patterns(pattern, patterns, &cfg);
+            check_extract(&patterns, group.as_deref());
+            let mut inputs = Inputs::new(
+                input_path,
+                get_binary_mode(binary, &record_separator),
+                get_encoding_of(encoding),
+                decompress,
+            );
+            inputs.set_globs(&globs);
+            inputs.mmap = !no_mmap;
+            let records = get_record_separator(record_start, record_separator, &cfg);
+
+            if frequency {
+                // Tally distinct matches in all inputs
+                let mut results = search_inputs(
+                    inputs,
+                    &patterns,
+                    &ignore_case,
+                    &overlapping,
+                    &false,
+                    &records,
+                )
+                .unwrap();
+                if group.is_some() {
+                    results.extract(group.as_deref());
+                }
 
-            // Count pattern
-            let results = count(&mut reader, &pattern, &ignore_case).unwrap();
+                // Get writer
+                let mut writer =
+                    get_writer(output_path, output_format, color, output_line_number, &cfg)
+                        .unwrap();
+
+                // Output results
+                write_frequency_results(frequency_of(&results, top), &mut writer);
+                return Ok(());
+            }
+
+            // Count patterns in all inputs
+            let tag_path = inputs.has_multiple();
+            let results = count_inputs(
+                inputs,
+                tag_path,
+                &patterns,
+                &ignore_case,
+                &overlapping,
+                &output_line_number,
+                &records,
+            )
+            .unwrap();
 
             // Get writer
-            let mut writer = get_writer(
-                output_path,
-                output_format,
-                output_line_number,
-                cfg.get_content_color().unwrap(),
-                cfg.get_match_color().unwrap(),
+            let mut writer =
+                get_writer(output_path, output_format, color, output_line_number, &cfg).unwrap();
+
+            // Output results
+            write_count_results(results, &mut writer);
+        }
+        Some(Command::Replace {
+            pattern,
+            replacement,
+            ignore_case,
+            input_path,
+            encoding,
+            output_path,
+            output_format,
+            color,
+            in_place,
+            backup,
+            dry_run,
+        }) => {
+            let pattern = get_pattern(&pattern, &cfg);
+
+            if in_place || dry_run {
+                // Edit input files, or only get diff of changes
+                let results = edit_inputs(
+                    input_path,
+                    &pattern,
+                    &replacement,
+                    &ignore_case,
+                    backup.as_deref(),
+                    dry_run,
+                )
+                .unwrap();
+
+                if dry_run {
+                    // Get writer
+                    let mut writer =
+                        get_writer(output_path, output_format, color, false, &cfg).unwrap();
+
+                    // Output diff
+                    write_replace_results(results, &mut writer);
+                }
+                return Ok(());
+            }
+
+            // Replace pattern in all inputs
+            let results = replace_inputs(
+                input_path,
+                get_encoding_of(encoding),
+                &pattern,
+                &replacement,
+                &ignore_case,
             )
             .unwrap();
 
+            // Get writer
+            let mut writer = get_writer(output_path, output_format, color, false, &cfg).unwrap();
+
             // Output results
-            write_count_results(results.to_string(), &mut writer);
+            write_replace_results(results, &mut writer);
+        }
+        Some(Command::Transform {
+            pattern,
+            operations,
+            ignore_case,
+            input_path,
+            encoding,
+            output_path,
+            output_format,
+            color,
+        }) => {
+            let pattern = pattern.map(|pattern| get_pattern(&pattern, &cfg));
+
+            // Transform all inputs
+            let results = transform_inputs(
+                input_path,
+                get_encoding_of(encoding),
+                pattern.as_deref(),
+                &ignore_case,
+                &operations,
+            )
+            .unwrap();
+
+            // Get writer
+            let mut writer = get_writer(output_path, output_format, color, false, &cfg).unwrap();
+
+            // Output results, same as replaced content
+            write_replace_results(results, &mut writer);
+        }
+        Some(Command::Redact {
+            patterns,
+            detectors,
+            no_detectors,
+            mask,
+            mask_text,
+            ignore_case,
+            input_path,
+            encoding,
+            output_path,
+            output_format,
+            color,
+        }) => {
+            let encoding = get_encoding_of(encoding);
+
+            // Get redactor
+            let mut redactor = Redactor::new(mask, mask_text);
+            if !no_detectors {
+                let detectors = if detectors.is_empty() {
+                    Detector::all()
+                } else {
+                    detectors
+                };
+                for detector in detectors {
+                    redactor.add_detector(detector);
+                }
+            }
+            for pattern in &patterns {
+                let pattern = get_pattern(pattern, &cfg);
+                redactor.add_pattern(&pattern, &ignore_case);
+            }
+
+            // Get writer
+            let plain = matches!(output_format, OutputFormat::Plain);
+            let mut writer = get_writer(output_path, output_format, color, false, &cfg).unwrap();
+
+            if plain {
+                // Stream redacted lines, counts go to stderr to keep the output clean
+                redact_inputs(input_path, encoding, &mut redactor, |line| {
+                    writer.write_content(&line.to_string())
+                })
+                .unwrap();
+                for (category, count) in &redactor.counts {
+                    eprintln!("Redacted {}: {}", category, count);
+                }
+            } else {
+                let mut content = String::new();
+                redact_inputs(input_path, encoding, &mut redactor, |line| {
+                    content.push_str(line)
+                })
+                .unwrap();
+
+                // Output results
+                let results = RedactResults {
+                    content,
+                    counts: redactor.counts,
+                };
+                write_redact_results(results, &mut writer);
+            }
+        }
+        Some(Command::Watch {
+            pattern,
+            patterns,
+            ignore_case,
+            overlapping,
+            input_path,
+            count,
+            color,
+            output_line_number,
+        }) => {
+            let patterns = get_patterns(pattern, patterns, &cfg);
+
+            // Get writer, console is redrawn only if it is a terminal
+            let mut writer =
+                get_writer(None, OutputFormat::Plain, color, output_line_number, &cfg).unwrap();
+            let redraw = stdout().is_terminal();
+
+            let paths: Vec<String> = input_path
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            let mut previous_results: Option<CountResults> = None;
+            watch_inputs(&input_path, |run| {
+                if redraw {
+                    writer.write_raw(CLEAR_SCREEN);
+                } else if run > 1 {
+                    writer.write_raw("\n");
+                }
+                writer.write_content(&format!("Watching {}, run {}\n\n", paths.join(", "), run));
+
+                // Removed files are skipped until created again, files are changing so they
+                // are never memory-mapped
+                let existing_paths: Vec<PathBuf> = input_path
+                    .iter()
+                    .filter(|path| path.exists())
+                    .cloned()
+                    .collect();
+                if existing_paths.is_empty() {
+                    writer.write_content(&"No input files\n".to_string());
+                    writer.flush();
+                    return;
+                }
+                let mut inputs = Inputs::new(existing_paths, BinaryMode::Report, None, false);
+                inputs.mmap = false;
+
+                if count {
+                    // Count patterns, showing changes since previous run, files are tagged
+                    // as configured so removed files are counted as 0
+                    let tag_path = input_path.len() > 1 || inputs.has_multiple();
+                    let results = count_inputs(
+                        inputs,
+                        tag_path,
+                        &patterns,
+                        &ignore_case,
+                        &overlapping,
+                        &output_line_number,
+                        &None,
+                    )
+                    .unwrap();
+                    let has_breakdown = results.has_breakdown();
+                    write_count_results(results.clone(), &mut writer);
+                    if !has_breakdown {
+                        writer.write_content(&"\n".to_string());
+                    }
+                    if let Some(previous_results) = &previous_results {
+                        writer.write_content(&"\n".to_string());
+                        write_count_delta(
+                            &CountDelta::new(previous_results, &results),
+                            &mut writer,
+                        );
+                    }
+                    previous_results = Some(results);
+                } else {
+                    let results =
+                        search_inputs(inputs, &patterns, &ignore_case, &overlapping, &false, &None)
+                            .unwrap();
+                    write_search_results(results, &mut writer);
+                }
+                writer.flush();
+            });
         }
+        Some(Command::Patterns { command }) => match command {
+            PatternsCommand::List => list_patterns(&cfg),
+        },
         None => {
-            // Get reader, ie content
-            let mut reader = get_reader(args.input_path).unwrap();
+            let patterns = get_patterns(args.pattern, args.patterns, &cfg);
+            check_extract(&patterns, args.extract.as_deref());
+
+            if args.follow {
+                check_follow_format(&args.output_format);
 
-            // Search pattern
-            let results = search(&mut reader, &args.pattern, &args.ignore_case).unwrap();
+                // Get writer
+                let mut writer = get_writer(
+                    args.output_path,
+                    args.output_format,
+                    args.color,
+                    args.output_line_number,
+                    &cfg,
+                )
+                .unwrap();
+
+                // Output results of appended lines as they come
+                follow_inputs(
+                    args.input_path,
+                    args.from_end,
+                    &patterns,
+                    &args.ignore_case,
+                    &args.overlapping,
+                    |mut results| {
+                        if args.only_matching || args.extract.is_some() {
+                            results.extract(args.extract.as_deref());
+                        }
+                        write_search_results(results, &mut writer);
+                        writer.flush();
+                    },
+                )
+                .unwrap();
+                return Ok(());
+            }
+
+            // Search patterns in all inputs
+            let mut inputs = Inputs::new(
+                args.input_path,
+                get_binary_mode(args.binary, &args.record_separator),
+                get_encoding_of(args.encoding),
+                args.decompress,
+            );
+            inputs.set_globs(&args.globs);
+            inputs.mmap = !args.no_mmap;
+            let records = get_record_separator(args.record_start, args.record_separator, &cfg);
+            let mut results = search_inputs(
+                inputs,
+                &patterns,
+                &args.ignore_case,
+                &args.overlapping,
+                &args.multiline,
+                &records,
+            )
+            .unwrap();
+            if args.only_matching || args.extract.is_some() {
+                results.extract(args.extract.as_deref());
+            }
 
             // Get writer
             let mut writer = get_writer(
                 args.output_path,
                 args.output_format,
+                args.color,
                 args.output_line_number,
-                cfg.get_content_color().unwrap(),
-                cfg.get_match_color().unwrap(),
+                &cfg,
             )
             .unwrap();
 
@@ -128,3 +612,402 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Exit with usage error, ie for invalid configuration or arguments
+fn exit_with_error(error: impl Display) -> ! {
+    Cli::command()
+        .error(ErrorKind::ValueValidation, error)
+        .exit()
+}
+
+/// Get pattern followed by additional patterns
+///
+/// Named patterns, ie `@email`, are expanded.
+fn get_patterns(pattern: String, patterns: Vec<String>, cfg: &Config) -> Vec<String> {
+    let mut all_patterns = vec![pattern];
+    all_patterns.extend(patterns);
+    all_patterns
+        .iter()
+        .map(|pattern| get_pattern(pattern, cfg))
+        .collect()
+}
+
+/// Get pattern, expanding named pattern
+///
+/// Pattern is compiled once, so invalid pattern is reported before searching.
+fn get_pattern(pattern: &str, cfg: &Config) -> String {
+    let pattern = expand_named_pattern(pattern, cfg);
+    compile_pattern(&pattern);
+    pattern
+}
+
+fn expand_named_pattern(pattern: &str, cfg: &Config) -> String {
+    expand_pattern(pattern, cfg.get_patterns()).unwrap_or_else(|error| exit_with_error(error))
+}
+
+fn compile_pattern(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|error| exit_with_error(error))
+}
+
+/// Check that extracted capture group exists, before searching
+fn check_extract(patterns: &[String], group: Option<&str>) {
+    if let Some(group) = group {
+        check_group(patterns, group).unwrap_or_else(|error| exit_with_error(error));
+    }
+}
+
+/// Check that results are followed in plain format, other formats are whole documents
+fn check_follow_format(output_format: &OutputFormat) {
+    if !matches!(output_format, OutputFormat::Plain) {
+        exit_with_error("--follow supports only plain output format");
+    }
+}
+
+/// Get separator of records, if records are searched rather than lines
+///
+/// Named patterns are expanded in record start.
+fn get_record_separator(
+    record_start: Option<String>,
+    record_separator: Option<String>,
+    cfg: &Config,
+) -> Option<RecordSeparator> {
+    if let Some(record_start) = record_start {
+        let pattern = expand_named_pattern(&record_start, cfg);
+        return Some(RecordSeparator::Start(compile_pattern(&pattern)));
+    }
+    record_separator.map(|separator| RecordSeparator::parse(&separator))
+}
+
+/// Get handling of binary inputs
+///
+/// Binary inputs are reported by default, but searched as text with `nul` records.
+fn get_binary_mode(binary: Option<BinaryMode>, record_separator: &Option<String>) -> BinaryMode {
+    binary.unwrap_or(match record_separator.as_deref() {
+        Some(separator) if RecordSeparator::parse(separator).is_nul() => BinaryMode::Text,
+        _ => BinaryMode::Report,
+    })
+}
+
+/// Get input encoding by label, otherwise it is detected
+fn get_encoding_of(encoding: Option<String>) -> Option<&'static Encoding> {
+    encoding.map(|label| get_encoding(&label).unwrap_or_else(|error| exit_with_error(error)))
+}
+
+/// List built-in patterns followed by user aliases
+fn list_patterns(cfg: &Config) {
+    let aliases = cfg.get_patterns();
+    let width = BUILTIN_PATTERNS
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(aliases.keys().map(|name| name.len()))
+        .max()
+        .unwrap_or(0);
+
+    for (name, pattern) in BUILTIN_PATTERNS {
+        println!("@{:<width$}  builtin  {}", name, pattern, width = width);
+    }
+    for (name, pattern) in aliases {
+        println!("@{:<width$}  user     {}", name, pattern, width = width);
+    }
+}
+
+/// Search patterns in stdin or every input file
+///
+/// Results are tagged with file path when there are multiple input files.
+fn search_inputs(
+    inputs: Inputs,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+    multiline: &bool,
+    records: &Option<RecordSeparator>,
+) -> Result<SearchResults> {
+    let search_reader = |mut reader: &mut dyn BufRead| {
+        if let Some(separator) = records {
+            search_records(&mut reader, patterns, ignore_case, overlapping, separator)
+        } else if *multiline {
+            search_multiline(&mut reader, patterns, ignore_case, overlapping)
+        } else {
+            search_patterns(&mut reader, patterns, ignore_case, overlapping)
+        }
+    };
+    let search = |content: Content| match content {
+        // Only matching lines of mapped content are decoded
+        Content::Mapped(bytes) if records.is_none() && !*multiline => {
+            Ok(search_bytes(bytes, patterns, ignore_case, overlapping))
+        }
+        Content::Mapped(bytes) => search_reader(&mut LossyReader::new(bytes)),
+        Content::Reader(reader) => search_reader(reader),
+    };
+
+    let tag_path = inputs.has_multiple();
+    let mut results = SearchResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns.to_vec();
+    }
+    read_inputs(&inputs, |path, content, is_binary| {
+        if is_binary && inputs.binary == BinaryMode::Skip {
+            info!("Skipping binary input {}", path);
+            return Ok(());
+        }
+
+        let input_results = search(content)?;
+        if is_binary && inputs.binary == BinaryMode::Report {
+            if !input_results.results.is_empty() {
+                eprintln!("{}: binary file matches", path);
+            }
+            return Ok(());
+        }
+        for mut result in input_results.results {
+            if tag_path {
+                result.set_path(path.clone());
+            }
+            results.add_result(result);
+        }
+        Ok(())
+    })?;
+
+    Ok(results)
+}
+
+/// Count patterns in stdin or every input file
+///
+/// Counts are broken down per file when tagged with path, ie there are multiple input files.
+fn count_inputs(
+    inputs: Inputs,
+    tag_path: bool,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+    per_line: &bool,
+    records: &Option<RecordSeparator>,
+) -> Result<CountResults> {
+    let count_reader = |mut reader: &mut dyn BufRead| match records {
+        Some(separator) => count_records(
+            &mut reader,
+            patterns,
+            ignore_case,
+            overlapping,
+            per_line,
+            separator,
+        ),
+        None => count_breakdown(&mut reader, patterns, ignore_case, overlapping, per_line),
+    };
+    let count = |content: Content| match content {
+        Content::Mapped(bytes) => count_reader(&mut LossyReader::new(bytes)),
+        Content::Reader(reader) => count_reader(reader),
+    };
+
+    let mut results = CountResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns
+            .iter()
+            .map(|pattern| PatternCount::new(pattern.to_string(), 0))
+            .collect();
+    }
+    read_inputs(&inputs, |path, content, is_binary| {
+        if is_binary && inputs.binary == BinaryMode::Skip {
+            info!("Skipping binary input {}", path);
+            return Ok(());
+        }
+
+        let file_results = count(content)?;
+        if is_binary && inputs.binary == BinaryMode::Report {
+            if file_results.total > 0 {
+                eprintln!("{}: binary file matches", path);
+            }
+            return Ok(());
+        }
+
+        if tag_path {
+            results.add_file(path, file_results);
+        } else {
+            results = file_results;
+        }
+        Ok(())
+    })?;
+
+    Ok(results)
+}
+
+/// Follow input files, searching patterns in lines appended to them
+///
+/// Results of each batch of appended lines are passed to callback right away, tagged with
+/// file path when there are multiple input files.
+fn follow_inputs<F: FnMut(SearchResults)>(
+    input_paths: Vec<PathBuf>,
+    from_end: bool,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+    mut callback: F,
+) -> Result<()> {
+    let tag_path = input_paths.len() > 1;
+    let mut followers = vec![];
+    for input_path in &input_paths {
+        let follower = Follower::new(input_path, from_end)
+            .with_context(|| format!("Error reading file {}", input_path.display()))
+            .unwrap();
+        followers.push((input_path.display().to_string(), follower));
+    }
+
+    loop {
+        for (path, follower) in &mut followers {
+            for chunk in follower.read()? {
+                let mut chunk_results =
+                    search_patterns(&mut &chunk.content[..], patterns, ignore_case, overlapping)?;
+                if chunk_results.results.is_empty() {
+                    continue;
+                }
+
+                for result in &mut chunk_results.results {
+                    result.line += chunk.line - 1;
+                    if tag_path {
+                        result.set_path(path.clone());
+                    }
+                }
+                callback(chunk_results);
+            }
+        }
+
+        sleep(POLL_INTERVAL);
+    }
+}
+
+/// Run callback at start and whenever any input file changes, with number of the run
+fn watch_inputs<F: FnMut(usize)>(input_paths: &[PathBuf], mut callback: F) {
+    let mut watcher = Watcher::new(input_paths);
+    let mut run = 1;
+    callback(run);
+
+    loop {
+        sleep(POLL_INTERVAL);
+        if watcher.has_changed() {
+            run += 1;
+            callback(run);
+        }
+    }
+}
+
+/// Replace pattern in stdin or every input file
+fn replace_inputs(
+    input_paths: Vec<PathBuf>,
+    encoding: Option<&'static Encoding>,
+    pattern: &str,
+    replacement: &str,
+    ignore_case: &bool,
+) -> Result<String> {
+    if input_paths.is_empty() {
+        let mut reader = get_reader(None, encoding).unwrap();
+        return replace(&mut reader, pattern, replacement, ignore_case);
+    }
+
+    let mut results = String::new();
+    for input_path in input_paths {
+        // Get reader, ie content
+        let mut reader = get_reader(Some(input_path), encoding).unwrap();
+        results.push_str(&replace(&mut reader, pattern, replacement, ignore_case)?);
+    }
+
+    Ok(results)
+}
+
+/// Transform all inputs, ie concatenated transformed content
+fn transform_inputs(
+    input_paths: Vec<PathBuf>,
+    encoding: Option<&'static Encoding>,
+    pattern: Option<&str>,
+    ignore_case: &bool,
+    operations: &[Operation],
+) -> Result<String> {
+    if input_paths.is_empty() {
+        let mut reader = get_reader(None, encoding).unwrap();
+        return transform_lines(&mut reader, pattern, ignore_case, operations);
+    }
+
+    let mut results = String::new();
+    for input_path in input_paths {
+        // Get reader, ie content
+        let mut reader = get_reader(Some(input_path), encoding).unwrap();
+        results.push_str(&transform_lines(
+            &mut reader,
+            pattern,
+            ignore_case,
+            operations,
+        )?);
+    }
+
+    Ok(results)
+}
+
+/// Redact stdin or every input file line by line
+fn redact_inputs<F: FnMut(&str)>(
+    input_paths: Vec<PathBuf>,
+    encoding: Option<&'static Encoding>,
+    redactor: &mut Redactor,
+    mut callback: F,
+) -> Result<()> {
+    if input_paths.is_empty() {
+        let mut reader = get_reader(None, encoding).unwrap();
+        return redactor.redact_lines(&mut reader, callback);
+    }
+
+    for input_path in input_paths {
+        // Get reader, ie content
+        let mut reader = get_reader(Some(input_path), encoding).unwrap();
+        redactor.redact_lines(&mut reader, &mut callback)?;
+    }
+
+    Ok(())
+}
+
+/// Replace pattern in input files in place
+///
+/// Only changed files are written. In dry run mode nothing is written and unified diff
+/// of all changes is returned.
+fn edit_inputs(
+    input_paths: Vec<PathBuf>,
+    pattern: &str,
+    replacement: &str,
+    ignore_case: &bool,
+    backup_suffix: Option<&str>,
+    dry_run: bool,
+) -> Result<String> {
+    let mut results = String::new();
+    for input_path in input_paths {
+        // Compressed and binary files are never edited
+        if Compression::from_path(&input_path).is_some() {
+            warn!("Skipping compressed file {}", input_path.display());
+            continue;
+        }
+        // Content is edited as bytes, so invalid UTF-8 is kept as it is, and written back
+        // with its BOM and encoding
+        let file_content = read(&input_path)
+            .with_context(|| format!("Error reading file {}", input_path.display()))
+            .unwrap();
+        let encoding = FileEncoding::detect(&file_content);
+        let Some(original) = encoding.decode(&file_content) else {
+            warn!("Skipping malformed UTF-16 file {}", input_path.display());
+            continue;
+        };
+        if is_binary(&original) {
+            warn!("Skipping binary file {}", input_path.display());
+            continue;
+        }
+
+        let changed = replace_bytes(&original, pattern, replacement, ignore_case);
+
+        if dry_run {
+            results.push_str(&get_diff(
+                &input_path,
+                &String::from_utf8_lossy(&original),
+                &String::from_utf8_lossy(&changed),
+            ));
+        } else if *original != changed {
+            write_file(&input_path, &encoding.encode(&changed), backup_suffix)?;
+            info!("Edited file {}", input_path.display());
+        }
+    }
+
+    Ok(results)
+}
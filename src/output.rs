@@ -1,55 +1,358 @@
+use serde::Serialize;
 use serde_json::{json, to_string};
 
+use crate::count_results::{CountDelta, CountResults};
+use crate::frequency::FrequencyResults;
+use crate::output_csv;
 use crate::output_format::OutputFormat;
+use crate::output_html;
+use crate::output_markdown;
 use crate::output_writer::OutputWriter;
-use crate::search_results::SearchResults;
+use crate::redact::RedactResults;
+use crate::search_results::{Match, SearchResult, SearchResults};
 
 /// Write search results
 pub fn write_search_results(results: SearchResults, writer: &mut OutputWriter) {
     match writer.format {
-        OutputFormat::Plain {} => {
+        OutputFormat::Plain => {
             for result in results.results {
-                // Line number
-                if !writer.style.is_none() && writer.style.as_ref().unwrap().line_number {
-                    writer.write_content(&format!("{}: ", result.line.to_string()));
+                // Extracted values, one per line
+                if !result.extracted.is_empty() {
+                    for value in &result.extracted {
+                        write_prefix(&result, result.line, writer);
+                        writer.write_match(value);
+                        writer.write_content(&"\n".to_string());
+                    }
+                    continue;
                 }
 
+                // Content can span multiple lines in multiline search
+                let mut line = result.line;
+                write_prefix(&result, line, writer);
+
                 // Write content before and matches
                 let mut prev_m_end_pos = 0usize;
-                for m in result.matches {
-                    let content_before =
-                        &result.content.as_str()[prev_m_end_pos..m.start_pos].to_string();
-                    prev_m_end_pos = m.end_pos;
+                for m in &result.matches {
+                    // Overlapping match, highlight only the part after previous match
+                    if m.start_pos < prev_m_end_pos {
+                        if m.end_pos > prev_m_end_pos {
+                            let content_after = &result.content[prev_m_end_pos..m.end_pos];
+                            write_lines(content_after, Some(m.pattern), &result, &mut line, writer);
+                            prev_m_end_pos = m.end_pos;
+                        }
+                        continue;
+                    }
 
-                    writer.write_content(&content_before);
-                    writer.write_match(&m.content);
+                    let content_before = &result.content[prev_m_end_pos..m.start_pos];
+                    write_lines(content_before, None, &result, &mut line, writer);
+                    if m.location.is_some() {
+                        let content = &result.content[m.start_pos..m.end_pos];
+                        write_lines(content, Some(m.pattern), &result, &mut line, writer);
+                    } else {
+                        write_match(m, writer);
+                    }
+                    prev_m_end_pos = m.end_pos;
                 }
 
                 // Write remaining content
-                let remaining_content = &result.content.as_str()[prev_m_end_pos..].to_string();
-                writer.write_content(&remaining_content);
+                let remaining_content = &result.content[prev_m_end_pos..];
+                write_lines(remaining_content, None, &result, &mut line, writer);
 
                 // Close the line
                 writer.write_content(&"\n".to_string())
             }
         }
-        OutputFormat::Json {} => {
+        OutputFormat::Json => {
             writer.write_content(&to_string(&results).unwrap());
         }
+        OutputFormat::Yaml => {
+            writer.write_content(&serde_yaml::to_string(&results).unwrap());
+        }
+        OutputFormat::Toml => {
+            writer.write_content(&toml::to_string(&results).unwrap());
+        }
+        OutputFormat::Html => {
+            output_html::write_search_results(&results, writer);
+        }
+        OutputFormat::Markdown => {
+            output_markdown::write_search_results(&results, writer);
+        }
+        OutputFormat::Csv => {
+            output_csv::write_search_results(&results, writer);
+        }
+    }
+}
+
+/// Write file path and line number of the result
+fn write_prefix(result: &SearchResult, line: usize, writer: &mut OutputWriter) {
+    // File path
+    if let Some(path) = &result.path {
+        writer.write_path(path);
+        writer.write_separator(&": ".to_string());
+    }
+
+    // Line number
+    if writer.style.is_some() && writer.style.as_ref().unwrap().line_number {
+        writer.write_line_number(&line.to_string());
+        writer.write_separator(&": ".to_string());
+    }
+}
+
+/// Write content or match of the pattern, which can span lines
+///
+/// Every following line starts with prefix, so styles never span lines.
+fn write_lines(
+    content: &str,
+    pattern: Option<usize>,
+    result: &SearchResult,
+    line: &mut usize,
+    writer: &mut OutputWriter,
+) {
+    for (idx, part) in content.split('\n').enumerate() {
+        if idx > 0 {
+            writer.write_content(&"\n".to_string());
+            *line += 1;
+            write_prefix(result, *line, writer);
+        }
+
+        let part = part.trim_end_matches('\r').to_string();
+        if part.is_empty() {
+            continue;
+        }
+        match pattern {
+            Some(pattern) => writer.write_pattern_match(&part, pattern),
+            None => writer.write_content(&part),
+        }
+    }
+}
+
+/// Write match, highlighting capture groups
+fn write_match(m: &Match, writer: &mut OutputWriter) {
+    if m.groups.is_empty() {
+        writer.write_pattern_match(&m.content, m.pattern);
+        return;
+    }
+
+    // Nested groups are highlighted as part of the outer group
+    let mut prev_end_pos = m.start_pos;
+    for group in &m.groups {
+        if group.start_pos < prev_end_pos {
+            continue;
+        }
+        let content_before = &m.content[prev_end_pos - m.start_pos..group.start_pos - m.start_pos];
+        if !content_before.is_empty() {
+            writer.write_pattern_match(&content_before.to_string(), m.pattern);
+        }
+        if !group.content.is_empty() {
+            writer.write_group(&group.content, m.pattern, group.index);
+        }
+        prev_end_pos = group.end_pos;
+    }
+
+    let remaining_content = &m.content[prev_end_pos - m.start_pos..];
+    if !remaining_content.is_empty() {
+        writer.write_pattern_match(&remaining_content.to_string(), m.pattern);
+    }
+}
+
+/// Count results in structured formats
+///
+/// Total is kept as `results`, as before breakdown was added, followed by counts of
+/// matches and lines with matches, and breakdown if any.
+#[derive(Serialize)]
+struct CountOutput<'a> {
+    results: String,
+    #[serde(flatten)]
+    counts: &'a CountResults,
+}
+
+impl<'a> CountOutput<'a> {
+    fn new(counts: &'a CountResults) -> Self {
+        Self {
+            results: counts.total.to_string(),
+            counts,
+        }
     }
 }
 
 ///Write count results
-pub fn write_count_results(results: String, writer: &mut OutputWriter) {
+pub fn write_count_results(results: CountResults, writer: &mut OutputWriter) {
+    match writer.format {
+        OutputFormat::Plain => {
+            // Only total without breakdown
+            if !results.has_breakdown() {
+                writer.write_content(&results.total.to_string());
+                return;
+            }
+
+            for line_count in &results.line_counts {
+                if let Some(path) = &line_count.path {
+                    writer.write_path(path);
+                    writer.write_separator(&": ".to_string());
+                }
+                writer.write_line_number(&line_count.line.to_string());
+                writer.write_separator(&": ".to_string());
+                writer.write_content(&format!("{}\n", line_count.total));
+            }
+            for file in &results.files {
+                writer.write_path(&file.path);
+                writer.write_separator(&": ".to_string());
+                writer.write_content(&format!(
+                    "{}\n",
+                    get_count_summary(file.total, file.lines, results.get_unit())
+                ));
+            }
+            for (idx, pattern) in results.patterns.iter().enumerate() {
+                writer.write_pattern_match(&pattern.pattern, idx);
+                writer.write_separator(&": ".to_string());
+                writer.write_content(&format!("{}\n", pattern.total));
+            }
+            writer.write_content(&format!(
+                "Total: {}\n",
+                get_count_summary(results.total, results.lines, results.get_unit())
+            ));
+        }
+        OutputFormat::Json => {
+            writer.write_content(&to_string(&CountOutput::new(&results)).unwrap());
+        }
+        OutputFormat::Yaml => {
+            writer.write_content(&serde_yaml::to_string(&CountOutput::new(&results)).unwrap());
+        }
+        OutputFormat::Toml => {
+            writer.write_content(&toml::to_string(&CountOutput::new(&results)).unwrap());
+        }
+        OutputFormat::Html => {
+            output_html::write_count_results(&results, writer);
+        }
+        OutputFormat::Markdown => {
+            output_markdown::write_count_results(&results, writer);
+        }
+        OutputFormat::Csv => {
+            output_csv::write_count_results(&results, writer);
+        }
+    }
+}
+
+/// Write change of counts since previous run, as plain text
+pub fn write_count_delta(delta: &CountDelta, writer: &mut OutputWriter) {
+    for (path, total) in &delta.files {
+        writer.write_path(path);
+        writer.write_separator(&": ".to_string());
+        writer.write_content(&format!("{:+}\n", total));
+    }
+    for (idx, (pattern, total)) in delta.patterns.iter().enumerate() {
+        writer.write_pattern_match(pattern, idx);
+        writer.write_separator(&": ".to_string());
+        writer.write_content(&format!("{:+}\n", total));
+    }
+    writer.write_content(&format!("Change: {:+}\n", delta.total));
+}
+
+fn get_count_summary(total: usize, lines: usize, unit: &str) -> String {
+    format!(
+        "{} {} in {} {}{}",
+        total,
+        if total == 1 { "match" } else { "matches" },
+        lines,
+        unit,
+        if lines == 1 { "" } else { "s" }
+    )
+}
+
+/// Write replace results
+pub fn write_replace_results(results: String, writer: &mut OutputWriter) {
+    let content_json = json!({
+        "results": &results
+    });
+
     match writer.format {
-        OutputFormat::Plain {} => {
+        OutputFormat::Plain => {
             writer.write_content(&results);
         }
-        OutputFormat::Json {} => {
-            let content_json = json!({
-                "results": &results
-            });
+        OutputFormat::Json => {
             writer.write_content(&to_string(&content_json).unwrap());
         }
+        OutputFormat::Yaml => {
+            writer.write_content(&serde_yaml::to_string(&content_json).unwrap());
+        }
+        OutputFormat::Toml => {
+            writer.write_content(&toml::to_string(&content_json).unwrap());
+        }
+        OutputFormat::Html => {
+            output_html::write_replace_results(&results, writer);
+        }
+        OutputFormat::Markdown => {
+            output_markdown::write_replace_results(&results, writer);
+        }
+        OutputFormat::Csv => {
+            output_csv::write_replace_results(&results, writer);
+        }
+    }
+}
+
+/// Write redact results
+///
+/// Plain format contains only redacted content, other formats include counts per category.
+pub fn write_redact_results(results: RedactResults, writer: &mut OutputWriter) {
+    match writer.format {
+        OutputFormat::Plain => {
+            writer.write_content(&results.content);
+        }
+        OutputFormat::Json => {
+            writer.write_content(&to_string(&results).unwrap());
+        }
+        OutputFormat::Yaml => {
+            writer.write_content(&serde_yaml::to_string(&results).unwrap());
+        }
+        OutputFormat::Toml => {
+            writer.write_content(&toml::to_string(&results).unwrap());
+        }
+        OutputFormat::Html => {
+            output_html::write_redact_results(&results, writer);
+        }
+        OutputFormat::Markdown => {
+            output_markdown::write_redact_results(&results, writer);
+        }
+        OutputFormat::Csv => {
+            output_csv::write_redact_results(&results, writer);
+        }
+    }
+}
+
+/// Write frequency results
+pub fn write_frequency_results(results: FrequencyResults, writer: &mut OutputWriter) {
+    match writer.format {
+        OutputFormat::Plain => {
+            // Counts aligned like `uniq -c`
+            let width = results
+                .results
+                .first()
+                .map(|f| f.count.to_string().len())
+                .unwrap_or(0);
+            for frequency in &results.results {
+                writer.write_line_number(&format!("{:>width$}", frequency.count, width = width));
+                writer.write_separator(&" ".to_string());
+                writer.write_match(&frequency.value);
+                writer.write_content(&"\n".to_string());
+            }
+        }
+        OutputFormat::Json => {
+            writer.write_content(&to_string(&results).unwrap());
+        }
+        OutputFormat::Yaml => {
+            writer.write_content(&serde_yaml::to_string(&results).unwrap());
+        }
+        OutputFormat::Toml => {
+            writer.write_content(&toml::to_string(&results).unwrap());
+        }
+        OutputFormat::Html => {
+            output_html::write_frequency_results(&results, writer);
+        }
+        OutputFormat::Markdown => {
+            output_markdown::write_frequency_results(&results, writer);
+        }
+        OutputFormat::Csv => {
+            output_csv::write_frequency_results(&results, writer);
+        }
     }
 }
@@ -0,0 +1,32 @@
+use std::env;
+
+use clap::ValueEnum;
+
+#[derive(Clone, PartialEq, ValueEnum)]
+pub enum OutputColor {
+    Auto,
+    Always,
+    Never,
+}
+
+impl OutputColor {
+    /// Check if colors should be written
+    ///
+    /// `auto` honors `NO_COLOR` and `CLICOLOR_FORCE` environment variables,
+    /// otherwise colors are written only to terminal.
+    pub fn is_enabled(&self, is_terminal: bool) -> bool {
+        match self {
+            OutputColor::Always => true,
+            OutputColor::Never => false,
+            OutputColor::Auto => {
+                if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                    return false;
+                }
+                if env::var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0") {
+                    return true;
+                }
+                is_terminal
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::count_results::CountResults;
+use crate::frequency::FrequencyResults;
+use crate::output_writer::OutputWriter;
+use crate::redact::RedactResults;
+use crate::search_results::SearchResults;
+
+/// Write search results as csv, one row per match or extracted value
+pub fn write_search_results(results: &SearchResults, writer: &mut OutputWriter) {
+    writer.write_raw("path,line,match\n");
+    for result in &results.results {
+        let path = result.path.as_deref().unwrap_or("");
+        let values: Vec<&str> = if result.extracted.is_empty() {
+            result.matches.iter().map(|m| m.content.as_str()).collect()
+        } else {
+            result.extracted.iter().map(|v| v.as_str()).collect()
+        };
+        for value in values {
+            write_row(writer, &[path, &result.line.to_string(), value]);
+        }
+    }
+}
+
+/// Write count results as csv, one row per line, file and pattern followed by total
+pub fn write_count_results(results: &CountResults, writer: &mut OutputWriter) {
+    let unit = results.get_unit();
+    writer.write_raw(&format!("kind,name,{}s,total\n", unit));
+    for line_count in &results.line_counts {
+        let name = match &line_count.path {
+            Some(path) => format!("{}:{}", path, line_count.line),
+            None => line_count.line.to_string(),
+        };
+        write_row(writer, &[unit, &name, "1", &line_count.total.to_string()]);
+    }
+    for file in &results.files {
+        write_row(
+            writer,
+            &[
+                "file",
+                &file.path,
+                &file.lines.to_string(),
+                &file.total.to_string(),
+            ],
+        );
+    }
+    for pattern in &results.patterns {
+        write_row(
+            writer,
+            &["pattern", &pattern.pattern, "", &pattern.total.to_string()],
+        );
+    }
+    write_row(
+        writer,
+        &[
+            "total",
+            "",
+            &results.lines.to_string(),
+            &results.total.to_string(),
+        ],
+    );
+}
+
+/// Write replace results as csv, one row per line
+pub fn write_replace_results(results: &str, writer: &mut OutputWriter) {
+    writer.write_raw("line,content\n");
+    for (idx, line) in results.lines().enumerate() {
+        write_row(writer, &[&(idx + 1).to_string(), line]);
+    }
+}
+
+/// Write redact results as csv, one row per redacted line followed by counts per category
+pub fn write_redact_results(results: &RedactResults, writer: &mut OutputWriter) {
+    writer.write_raw("kind,name,value\n");
+    for (idx, line) in results.content.lines().enumerate() {
+        write_row(writer, &["line", &(idx + 1).to_string(), line]);
+    }
+    for (category, count) in &results.counts {
+        write_row(writer, &["category", category, &count.to_string()]);
+    }
+}
+
+/// Write frequency results as csv
+pub fn write_frequency_results(results: &FrequencyResults, writer: &mut OutputWriter) {
+    writer.write_raw("value,count\n");
+    for frequency in &results.results {
+        write_row(writer, &[&frequency.value, &frequency.count.to_string()]);
+    }
+}
+
+fn write_row(writer: &mut OutputWriter, fields: &[&str]) {
+    let row: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+    writer.write_raw(&format!("{}\n", row.join(",")));
+}
+
+/// Quote field containing separator, quotes or line breaks
+pub fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -4,4 +4,9 @@ use clap::ValueEnum;
 pub enum OutputFormat {
     Plain,
     Json,
+    Html,
+    Markdown,
+    Yaml,
+    Toml,
+    Csv,
 }
@@ -0,0 +1,289 @@
+use crate::count_results::CountResults;
+use crate::frequency::FrequencyResults;
+use crate::output_style::TextStyle;
+use crate::output_writer::OutputWriter;
+use crate::redact::RedactResults;
+use crate::search_results::{Match, SearchResults};
+
+const DEFAULT_SECTION: &str = "Results";
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+h1{font-size:1.4em}h2{font-size:1.1em;margin-top:2em}\
+table{border-collapse:collapse}td,th{padding:2px 8px;text-align:left}\
+.summary td,.summary th{border:1px solid #ccc}\
+.lines td{font-family:monospace;white-space:pre-wrap;vertical-align:top}\
+.lines td.line{color:#888;text-align:right;user-select:none}\
+mark{background:none;font-weight:bold}\
+#filter{margin-bottom:1em;padding:4px;width:20em}";
+
+const SCRIPT: &str = "document.getElementById('filter').addEventListener('input',function(e){\
+var q=e.target.value.toLowerCase();\
+document.querySelectorAll('section').forEach(function(s){\
+var visible=0;\
+s.querySelectorAll('tr').forEach(function(r){\
+var show=r.textContent.toLowerCase().indexOf(q)!==-1;\
+r.style.display=show?'':'none';if(show){visible++;}});\
+s.style.display=visible>0?'':'none';});});";
+
+/// Write search results as a self-contained html document
+pub fn write_search_results(results: &SearchResults, writer: &mut OutputWriter) {
+    let sections = results.group_by_path();
+
+    write_header(writer, &results.pattern);
+
+    // Summary
+    writer.write_raw(
+        "<table class=\"summary\">\n<tr><th>File</th><th>Lines</th><th>Matches</th></tr>\n",
+    );
+    let mut total_lines = 0usize;
+    let mut total_matches = 0usize;
+    for (path, section_results) in &sections {
+        let matches: usize = section_results.iter().map(|r| r.matches.len()).sum();
+        total_lines += section_results.len();
+        total_matches += matches;
+        writer.write_raw(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(path.unwrap_or(DEFAULT_SECTION)),
+            section_results.len(),
+            matches
+        ));
+    }
+    writer.write_raw(&format!(
+        "<tr><th>Total</th><th>{}</th><th>{}</th></tr>\n</table>\n",
+        total_lines, total_matches
+    ));
+
+    // Per file sections
+    writer.write_raw("<input id=\"filter\" type=\"search\" placeholder=\"Filter lines\">\n");
+    for (path, section_results) in &sections {
+        writer.write_raw(&format!(
+            "<section>\n<h2>{}</h2>\n<table class=\"lines\">\n",
+            escape(path.unwrap_or(DEFAULT_SECTION))
+        ));
+        for result in section_results {
+            // Extracted values, one per row
+            if !result.extracted.is_empty() {
+                let match_css = get_css(writer.style.as_ref().and_then(|s| s.match_color));
+                for value in &result.extracted {
+                    writer.write_raw(&format!(
+                        "<tr><td class=\"line\">{}</td><td><mark style=\"{}\">{}</mark></td></tr>\n",
+                        result.line,
+                        match_css,
+                        escape(value)
+                    ));
+                }
+                continue;
+            }
+
+            writer.write_raw(&format!("<tr><td class=\"line\">{}</td><td>", result.line));
+
+            // Write content before and matches
+            let mut prev_m_end_pos = 0usize;
+            for m in &result.matches {
+                // Overlapping match, highlight only the part after previous match
+                if m.start_pos < prev_m_end_pos {
+                    if m.end_pos > prev_m_end_pos {
+                        let css = get_css(
+                            writer
+                                .style
+                                .as_ref()
+                                .and_then(|s| s.get_match_style(m.pattern)),
+                        );
+                        writer.write_raw(&format!(
+                            "<mark style=\"{}\">{}</mark>",
+                            css,
+                            escape(&result.content[prev_m_end_pos..m.end_pos])
+                        ));
+                        prev_m_end_pos = m.end_pos;
+                    }
+                    continue;
+                }
+
+                writer.write_raw(&escape(&result.content[prev_m_end_pos..m.start_pos]));
+                write_match(m, writer);
+                prev_m_end_pos = m.end_pos;
+            }
+
+            // Write remaining content
+            writer.write_raw(&escape(&result.content[prev_m_end_pos..]));
+            writer.write_raw("</td></tr>\n");
+        }
+        writer.write_raw("</table>\n</section>\n");
+    }
+
+    write_footer(writer, true);
+}
+
+/// Write count results as a self-contained html document
+pub fn write_count_results(results: &CountResults, writer: &mut OutputWriter) {
+    write_header(writer, &results.pattern);
+    let unit = if results.records { "Records" } else { "Lines" };
+
+    // Per file
+    if !results.files.is_empty() {
+        writer.write_raw(&format!(
+            "<table class=\"summary\">\n<tr><th>File</th><th>{}</th><th>Matches</th></tr>\n",
+            unit
+        ));
+        for file in &results.files {
+            writer.write_raw(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape(&file.path),
+                file.lines,
+                file.total
+            ));
+        }
+        writer.write_raw("</table>\n");
+    }
+
+    // Per pattern
+    if !results.patterns.is_empty() {
+        writer.write_raw("<table class=\"summary\">\n<tr><th>Pattern</th><th>Matches</th></tr>\n");
+        for (idx, pattern) in results.patterns.iter().enumerate() {
+            let css = get_css(writer.style.as_ref().and_then(|s| s.get_match_style(idx)));
+            writer.write_raw(&format!(
+                "<tr><td><mark style=\"{}\">{}</mark></td><td>{}</td></tr>\n",
+                css,
+                escape(&pattern.pattern),
+                pattern.total
+            ));
+        }
+        writer.write_raw("</table>\n");
+    }
+
+    // Per line
+    if !results.line_counts.is_empty() {
+        writer.write_raw("<table class=\"lines\">\n");
+        for line_count in &results.line_counts {
+            let path = line_count
+                .path
+                .as_deref()
+                .map(|path| format!("{}: ", escape(path)))
+                .unwrap_or_default();
+            writer.write_raw(&format!(
+                "<tr><td class=\"line\">{}{}</td><td>{}</td></tr>\n",
+                path, line_count.line, line_count.total
+            ));
+        }
+        writer.write_raw("</table>\n");
+    }
+
+    writer.write_raw(&format!(
+        "<table class=\"summary\">\n<tr><th>{}</th><td>{}</td></tr>\n<tr><th>Total</th><td>{}</td></tr>\n</table>\n",
+        unit, results.lines, results.total
+    ));
+    write_footer(writer, false);
+}
+
+/// Write replace results as a self-contained html document
+pub fn write_replace_results(results: &str, writer: &mut OutputWriter) {
+    write_header(writer, "Replace");
+    writer.write_raw(&format!("<pre>{}</pre>\n", escape(results)));
+    write_footer(writer, false);
+}
+
+/// Write frequency results as table
+pub fn write_frequency_results(results: &FrequencyResults, writer: &mut OutputWriter) {
+    write_header(writer, &results.pattern);
+    writer.write_raw("<table class=\"summary\">\n<tr><th>Value</th><th>Count</th></tr>\n");
+    let match_css = get_css(writer.style.as_ref().and_then(|s| s.match_color));
+    for frequency in &results.results {
+        writer.write_raw(&format!(
+            "<tr><td><mark style=\"{}\">{}</mark></td><td>{}</td></tr>\n",
+            match_css,
+            escape(&frequency.value),
+            frequency.count
+        ));
+    }
+    writer.write_raw(&format!(
+        "<tr><th>Total ({} distinct)</th><th>{}</th></tr>\n</table>\n",
+        results.distinct, results.total
+    ));
+    write_footer(writer, false);
+}
+
+/// Write redact results as counts per category table followed by redacted content
+pub fn write_redact_results(results: &RedactResults, writer: &mut OutputWriter) {
+    write_header(writer, "Redact");
+    writer.write_raw("<table class=\"summary\">\n<tr><th>Category</th><th>Redactions</th></tr>\n");
+    for (category, count) in &results.counts {
+        writer.write_raw(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>\n",
+            escape(category),
+            count
+        ));
+    }
+    writer.write_raw(&format!(
+        "<tr><th>Total</th><th>{}</th></tr>\n</table>\n",
+        results.counts.values().sum::<usize>()
+    ));
+    writer.write_raw(&format!("<pre>{}</pre>\n", escape(&results.content)));
+    write_footer(writer, false);
+}
+
+fn write_header(writer: &mut OutputWriter, title: &str) {
+    writer.write_raw(&format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>oko: {}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape(title),
+        STYLE,
+        escape(title)
+    ));
+}
+
+fn write_footer(writer: &mut OutputWriter, filter: bool) {
+    if filter {
+        writer.write_raw(&format!("<script>{}</script>\n", SCRIPT));
+    }
+    writer.write_raw("</body>\n</html>\n");
+}
+
+/// Write match, highlighting capture groups
+fn write_match(m: &Match, writer: &mut OutputWriter) {
+    let style = writer.style.as_ref();
+    let match_css = get_css(style.and_then(|s| s.get_match_style(m.pattern)));
+
+    let mut content = String::new();
+    let mut prev_end_pos = m.start_pos;
+    for group in &m.groups {
+        // Nested groups are highlighted as part of the outer group
+        if group.start_pos < prev_end_pos {
+            continue;
+        }
+        let group_css = get_css(style.and_then(|s| s.get_group_style(m.pattern, group.index)));
+        content.push_str(&escape(
+            &m.content[prev_end_pos - m.start_pos..group.start_pos - m.start_pos],
+        ));
+        content.push_str(&format!(
+            "<span style=\"{}\">{}</span>",
+            group_css,
+            escape(&group.content)
+        ));
+        prev_end_pos = group.end_pos;
+    }
+    content.push_str(&escape(&m.content[prev_end_pos - m.start_pos..]));
+
+    writer.write_raw(&format!("<mark style=\"{}\">{}</mark>", match_css, content));
+}
+
+fn get_css(style: Option<TextStyle>) -> String {
+    match style {
+        Some(style) => style.css(),
+        None => String::new(),
+    }
+}
+
+/// Escape html special characters
+pub fn escape(content: &str) -> String {
+    let mut escaped = String::with_capacity(content.len());
+    for c in content.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
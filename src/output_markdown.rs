@@ -0,0 +1,185 @@
+use crate::count_results::CountResults;
+use crate::frequency::FrequencyResults;
+use crate::output_writer::OutputWriter;
+use crate::redact::RedactResults;
+use crate::search_results::SearchResults;
+
+const DEFAULT_HEADING: &str = "Results";
+
+/// Write search results as markdown
+///
+/// Results are grouped by file under headings and matching lines are rendered in
+/// fenced code blocks, every line with its number. Emphasis is not rendered inside code
+/// fences, so matches are not bold.
+pub fn write_search_results(results: &SearchResults, writer: &mut OutputWriter) {
+    for (idx, (path, group)) in results.group_by_path().iter().enumerate() {
+        if idx > 0 {
+            writer.write_raw("\n");
+        }
+        writer.write_raw(&format!(
+            "## {}\n\n",
+            escape(path.unwrap_or(DEFAULT_HEADING))
+        ));
+
+        let fence = get_fence(group.iter().flat_map(|r| {
+            r.extracted
+                .iter()
+                .map(|v| v.as_str())
+                .chain([r.content.as_str()])
+        }));
+        // Results can span multiple lines, ie in multiline and record search
+        let line_width = group
+            .iter()
+            .map(|r| r.line + r.content.lines().count().max(1) - 1)
+            .max()
+            .unwrap_or(0)
+            .to_string()
+            .len();
+
+        writer.write_raw(&format!("{}text\n", fence));
+        for result in group {
+            // Extracted values, one per line
+            if !result.extracted.is_empty() {
+                for value in &result.extracted {
+                    writer.write_raw(&format!(
+                        "{:>width$}: {}\n",
+                        result.line,
+                        value,
+                        width = line_width
+                    ));
+                }
+                continue;
+            }
+
+            for (line_idx, line_content) in result.content.split('\n').enumerate() {
+                writer.write_raw(&format!(
+                    "{:>width$}: {}\n",
+                    result.line + line_idx,
+                    line_content.trim_end_matches('\r'),
+                    width = line_width
+                ));
+            }
+        }
+        writer.write_raw(&format!("{}\n", fence));
+    }
+}
+
+/// Write count results as markdown tables
+pub fn write_count_results(results: &CountResults, writer: &mut OutputWriter) {
+    let (unit, units) = if results.records {
+        ("Record", "Records")
+    } else {
+        ("Line", "Lines")
+    };
+    if !results.files.is_empty() {
+        writer.write_raw(&format!(
+            "| File | {} | Matches |\n| --- | ---: | ---: |\n",
+            units
+        ));
+        for file in &results.files {
+            writer.write_raw(&format!(
+                "| {} | {} | {} |\n",
+                escape(&file.path),
+                file.lines,
+                file.total
+            ));
+        }
+        writer.write_raw("\n");
+    }
+    if !results.patterns.is_empty() {
+        writer.write_raw("| Pattern | Matches |\n| --- | ---: |\n");
+        for pattern in &results.patterns {
+            writer.write_raw(&format!(
+                "| {} | {} |\n",
+                escape(&pattern.pattern),
+                pattern.total
+            ));
+        }
+        writer.write_raw("\n");
+    }
+    if !results.line_counts.is_empty() {
+        writer.write_raw(&format!("| {} | Matches |\n| ---: | ---: |\n", unit));
+        for line_count in &results.line_counts {
+            let line = match &line_count.path {
+                Some(path) => format!("{}:{}", escape(path), line_count.line),
+                None => line_count.line.to_string(),
+            };
+            writer.write_raw(&format!("| {} | {} |\n", line, line_count.total));
+        }
+        writer.write_raw("\n");
+    }
+    writer.write_raw("| Total |\n| ---: |\n");
+    writer.write_raw(&format!("| **{}** |\n", results.total));
+}
+
+/// Write replace results as fenced code block
+pub fn write_replace_results(results: &str, writer: &mut OutputWriter) {
+    let fence = get_fence(results.lines());
+    writer.write_raw(&format!("{}text\n", fence));
+    writer.write_raw(results);
+    if !results.is_empty() && !results.ends_with('\n') {
+        writer.write_raw("\n");
+    }
+    writer.write_raw(&format!("{}\n", fence));
+}
+
+/// Write frequency results as markdown table
+pub fn write_frequency_results(results: &FrequencyResults, writer: &mut OutputWriter) {
+    writer.write_raw("| Value | Count |\n| --- | ---: |\n");
+    for frequency in &results.results {
+        writer.write_raw(&format!(
+            "| {} | {} |\n",
+            escape(&frequency.value),
+            frequency.count
+        ));
+    }
+    writer.write_raw(&format!(
+        "| **Total ({} distinct)** | **{}** |\n",
+        results.distinct, results.total
+    ));
+}
+
+/// Write redact results as counts per category table followed by fenced code block
+pub fn write_redact_results(results: &RedactResults, writer: &mut OutputWriter) {
+    writer.write_raw("| Category | Redactions |\n| --- | ---: |\n");
+    for (category, count) in &results.counts {
+        writer.write_raw(&format!("| {} | {} |\n", escape(category), count));
+    }
+    writer.write_raw(&format!(
+        "| **Total** | **{}** |\n\n",
+        results.counts.values().sum::<usize>()
+    ));
+    write_replace_results(&results.content, writer);
+}
+
+/// Get code fence longer than any backtick sequence in the content
+fn get_fence<'a>(contents: impl Iterator<Item = &'a str>) -> String {
+    let mut longest = 0usize;
+    for content in contents {
+        let mut current = 0usize;
+        for c in content.chars() {
+            if c == '`' {
+                current += 1;
+                longest = longest.max(current);
+            } else {
+                current = 0;
+            }
+        }
+    }
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// Escape markdown special characters outside code blocks
+pub fn escape(content: &str) -> String {
+    let mut escaped = String::with_capacity(content.len());
+    for c in content.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -1,21 +1,324 @@
-use owo_colors::AnsiColors;
+use std::io::{Error, ErrorKind, Result};
+use std::str::FromStr;
+
+use owo_colors::{AnsiColors, DynColors, OwoColorize, Style, XtermColors};
 
 pub struct OutputStyle {
     pub line_number: bool,
-    pub content_color: Option<AnsiColors>,
-    pub match_color: Option<AnsiColors>,
+    pub content_color: Option<TextStyle>,
+    pub match_color: Option<TextStyle>,
+    pub line_number_color: Option<TextStyle>,
+    pub path_color: Option<TextStyle>,
+    pub separator_color: Option<TextStyle>,
+    pub match_palette: Vec<TextStyle>,
 }
 
 impl OutputStyle {
     pub fn new(
         line_number: bool,
-        content_color: Option<AnsiColors>,
-        match_color: Option<AnsiColors>,
+        content_color: Option<TextStyle>,
+        match_color: Option<TextStyle>,
     ) -> Self {
         Self {
             line_number,
             content_color,
             match_color,
+            line_number_color: None,
+            path_color: None,
+            separator_color: None,
+            match_palette: vec![],
+        }
+    }
+}
+
+impl OutputStyle {
+    /// Get style of a match of the pattern
+    ///
+    /// First pattern uses match color, other patterns use palette colors from the end.
+    pub fn get_match_style(&self, pattern: usize) -> Option<TextStyle> {
+        if pattern == 0 || self.match_palette.is_empty() {
+            return self.match_color;
+        }
+        let len = self.match_palette.len();
+        Some(self.match_palette[len - 1 - (pattern - 1) % len])
+    }
+
+    /// Get style of a capture group of a match of the pattern
+    ///
+    /// Capture groups use palette colors from the start and are underlined, so they never
+    /// look like a match of another pattern using the same palette color.
+    pub fn get_group_style(&self, pattern: usize, group: usize) -> Option<TextStyle> {
+        if self.match_palette.is_empty() {
+            return self.get_match_style(pattern);
+        }
+        let mut style = self.match_palette[(pattern + group - 1) % self.match_palette.len()];
+        style.underline = true;
+        Some(style)
+    }
+}
+
+/// Text style, ie foreground and background colors with text attributes
+///
+/// Example: `bold underline #ff8800 on 236`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextStyle {
+    pub color: Option<DynColors>,
+    pub background: Option<DynColors>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+impl TextStyle {
+    pub fn new(color: Option<DynColors>) -> Self {
+        Self {
+            color,
+            ..Default::default()
+        }
+    }
+
+    /// Parse style from space separated colors and attributes
+    ///
+    /// Colors are names (`green`, `bright green`), hex (`#00ff00`), rgb (`rgb(0,255,0)`)
+    /// or 256-color palette numbers (`0`-`255`). Background color is prefixed with `on`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut style = TextStyle::default();
+        let mut tokens = spec.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token {
+                "bold" => style.bold = true,
+                "italic" => style.italic = true,
+                "underline" => style.underline = true,
+                "on" => {
+                    let color = next_color(&mut tokens, spec)?;
+                    style.background = Some(color);
+                }
+                _ => {
+                    let color = parse_color(token, &mut tokens, spec)?;
+                    style.color = Some(color);
+                }
+            }
+        }
+        Ok(style)
+    }
+
+    /// Paint content with the style using terminal escape codes
+    pub fn paint(&self, content: &str) -> String {
+        // Color only style resets only foreground color
+        if self.background.is_none() && !self.bold && !self.italic && !self.underline {
+            return match self.color {
+                Some(color) => content.color(color).to_string(),
+                None => content.to_string(),
+            };
+        }
+
+        let mut style = Style::new();
+        if let Some(color) = self.color {
+            style = style.color(color);
+        }
+        if let Some(background) = self.background {
+            style = style.on_color(background);
+        }
+        if self.bold {
+            style = style.bold();
+        }
+        if self.italic {
+            style = style.italic();
+        }
+        if self.underline {
+            style = style.underline();
+        }
+        content.style(style).to_string()
+    }
+
+    /// Get css declarations for the style
+    pub fn css(&self) -> String {
+        let mut declarations: Vec<String> = vec![];
+        if let Some(color) = self.color {
+            declarations.push(format!("color:{}", css_color(color)));
+        }
+        if let Some(background) = self.background {
+            declarations.push(format!("background:{}", css_color(background)));
+        }
+        if self.bold {
+            declarations.push("font-weight:bold".to_string());
+        }
+        if self.italic {
+            declarations.push("font-style:italic".to_string());
+        }
+        if self.underline {
+            declarations.push("text-decoration:underline".to_string());
+        }
+        declarations.join(";")
+    }
+}
+
+impl FromStr for TextStyle {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        TextStyle::parse(spec)
+    }
+}
+
+impl From<AnsiColors> for TextStyle {
+    fn from(color: AnsiColors) -> Self {
+        TextStyle::new(Some(DynColors::Ansi(color)))
+    }
+}
+
+fn next_color<'a>(tokens: &mut impl Iterator<Item = &'a str>, spec: &str) -> Result<DynColors> {
+    match tokens.next() {
+        Some(token) => parse_color(token, tokens, spec),
+        None => Err(invalid_style(spec)),
+    }
+}
+
+fn parse_color<'a>(
+    token: &str,
+    tokens: &mut impl Iterator<Item = &'a str>,
+    spec: &str,
+) -> Result<DynColors> {
+    // Bright colors are written as two words
+    if token == "bright" {
+        let name = tokens.next().ok_or_else(|| invalid_style(spec))?;
+        return parse_color_name(&format!("bright {}", name)).ok_or_else(|| unknown_color(name));
+    }
+
+    if let Some(hex) = token.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| unknown_color(token));
+    }
+
+    if let Some(rgb) = token
+        .strip_prefix("rgb(")
+        .and_then(|rgb| rgb.strip_suffix(')'))
+    {
+        let values: Vec<Option<u8>> = rgb.split(',').map(|v| v.trim().parse().ok()).collect();
+        return match values[..] {
+            [Some(r), Some(g), Some(b)] => Ok(DynColors::Rgb(r, g, b)),
+            _ => Err(unknown_color(token)),
+        };
+    }
+
+    if let Ok(idx) = token.parse::<u8>() {
+        return Ok(DynColors::Xterm(XtermColors::from(idx)));
+    }
+
+    parse_color_name(token).ok_or_else(|| unknown_color(token))
+}
+
+fn parse_color_name(name: &str) -> Option<DynColors> {
+    let color = match name {
+        "black" => AnsiColors::Black,
+        "red" => AnsiColors::Red,
+        "green" => AnsiColors::Green,
+        "yellow" => AnsiColors::Yellow,
+        "blue" => AnsiColors::Blue,
+        "magenta" | "purple" => AnsiColors::Magenta,
+        "cyan" => AnsiColors::Cyan,
+        "white" => AnsiColors::White,
+        "default" => AnsiColors::Default,
+        "bright black" => AnsiColors::BrightBlack,
+        "bright red" => AnsiColors::BrightRed,
+        "bright green" => AnsiColors::BrightGreen,
+        "bright yellow" => AnsiColors::BrightYellow,
+        "bright blue" => AnsiColors::BrightBlue,
+        "bright magenta" | "bright purple" => AnsiColors::BrightMagenta,
+        "bright cyan" => AnsiColors::BrightCyan,
+        "bright white" => AnsiColors::BrightWhite,
+        _ => return None,
+    };
+    Some(DynColors::Ansi(color))
+}
+
+fn parse_hex(hex: &str) -> Option<DynColors> {
+    let expanded: String = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    let r = u8::from_str_radix(expanded.get(0..2)?, 16).ok()?;
+    let g = u8::from_str_radix(expanded.get(2..4)?, 16).ok()?;
+    let b = u8::from_str_radix(expanded.get(4..6)?, 16).ok()?;
+    Some(DynColors::Rgb(r, g, b))
+}
+
+fn unknown_color(color: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, format!("Unknown color {}", color))
+}
+
+fn invalid_style(spec: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, format!("Invalid style {}", spec))
+}
+
+/// Convert terminal color to css color
+fn css_color(color: DynColors) -> String {
+    let (r, g, b) = match color {
+        DynColors::Ansi(AnsiColors::Default) => return "inherit".to_string(),
+        DynColors::Ansi(ansi) => ansi_rgb(ansi),
+        DynColors::Xterm(xterm) => xterm_rgb(u8::from(xterm)),
+        DynColors::Rgb(r, g, b) => (r, g, b),
+        DynColors::Css(_) => return "inherit".to_string(),
+    };
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn ansi_rgb(color: AnsiColors) -> (u8, u8, u8) {
+    match color {
+        AnsiColors::Black => (0x00, 0x00, 0x00),
+        AnsiColors::Red => (0xcd, 0x00, 0x00),
+        AnsiColors::Green => (0x00, 0xa0, 0x00),
+        AnsiColors::Yellow => (0xb8, 0xa0, 0x00),
+        AnsiColors::Blue => (0x00, 0x00, 0xee),
+        AnsiColors::Magenta => (0xcd, 0x00, 0xcd),
+        AnsiColors::Cyan => (0x00, 0xa0, 0xa0),
+        AnsiColors::White => (0xa0, 0xa0, 0xa0),
+        AnsiColors::Default => (0x22, 0x22, 0x22),
+        AnsiColors::BrightBlack => (0x7f, 0x7f, 0x7f),
+        AnsiColors::BrightRed => (0xff, 0x00, 0x00),
+        AnsiColors::BrightGreen => (0x00, 0xd0, 0x00),
+        AnsiColors::BrightYellow => (0xd0, 0xd0, 0x00),
+        AnsiColors::BrightBlue => (0x5c, 0x5c, 0xff),
+        AnsiColors::BrightMagenta => (0xff, 0x00, 0xff),
+        AnsiColors::BrightCyan => (0x00, 0xd0, 0xd0),
+        AnsiColors::BrightWhite => (0xff, 0xff, 0xff),
+    }
+}
+
+fn xterm_rgb(idx: u8) -> (u8, u8, u8) {
+    const ANSI: [AnsiColors; 16] = [
+        AnsiColors::Black,
+        AnsiColors::Red,
+        AnsiColors::Green,
+        AnsiColors::Yellow,
+        AnsiColors::Blue,
+        AnsiColors::Magenta,
+        AnsiColors::Cyan,
+        AnsiColors::White,
+        AnsiColors::BrightBlack,
+        AnsiColors::BrightRed,
+        AnsiColors::BrightGreen,
+        AnsiColors::BrightYellow,
+        AnsiColors::BrightBlue,
+        AnsiColors::BrightMagenta,
+        AnsiColors::BrightCyan,
+        AnsiColors::BrightWhite,
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match idx {
+        0..=15 => ansi_rgb(ANSI[idx as usize]),
+        16..=231 => {
+            let idx = idx - 16;
+            (
+                LEVELS[(idx / 36) as usize],
+                LEVELS[((idx / 6) % 6) as usize],
+                LEVELS[(idx % 6) as usize],
+            )
+        }
+        _ => {
+            let gray = 8 + (idx - 232) * 10;
+            (gray, gray, gray)
         }
     }
 }
@@ -1,16 +1,15 @@
 use std::io::{Result, Write};
 
-use owo_colors::{AnsiColors, OwoColorize};
-
 use crate::output_destination::OutputDestination;
 use crate::output_format::OutputFormat;
-use crate::output_style::OutputStyle;
+use crate::output_style::{OutputStyle, TextStyle};
 
 pub struct OutputWriter<'a> {
     pub writer: Box<dyn Write + 'a>,
     pub destination: OutputDestination,
     pub format: OutputFormat,
     pub style: Option<OutputStyle>,
+    pub color: bool,
 }
 
 impl<'a> OutputWriter<'a> {
@@ -20,14 +19,22 @@ impl<'a> OutputWriter<'a> {
         format: OutputFormat,
         style: Option<OutputStyle>,
     ) -> Self {
+        // Colors are written to console by default
+        let color = destination == OutputDestination::Standard;
+
         Self {
             writer: sink,
             destination,
             format,
             style,
+            color,
         }
     }
 
+    pub fn set_color(&mut self, color: bool) {
+        self.color = color;
+    }
+
     pub fn write_content(&mut self, content: &String) {
         if self.style.is_none() {
             self.write(content, None).unwrap();
@@ -46,11 +53,51 @@ impl<'a> OutputWriter<'a> {
         }
     }
 
-    fn write(&mut self, content: &String, color: Option<AnsiColors>) -> Result<()> {
-        if self.destination == OutputDestination::Standard && !color.is_none() {
-            write!(self.writer, "{}", content.color(color.unwrap())).unwrap();
-        } else {
-            write!(self.writer, "{}", content).unwrap();
+    pub fn write_pattern_match(&mut self, content: &String, pattern: usize) {
+        let style = self.style.as_ref().and_then(|s| s.get_match_style(pattern));
+        self.write(content, style).unwrap();
+    }
+
+    pub fn write_group(&mut self, content: &String, pattern: usize, group: usize) {
+        let style = self
+            .style
+            .as_ref()
+            .and_then(|s| s.get_group_style(pattern, group));
+        self.write(content, style).unwrap();
+    }
+
+    pub fn write_line_number(&mut self, content: &String) {
+        let style = self.style.as_ref().and_then(|s| s.line_number_color);
+        self.write(content, style).unwrap();
+    }
+
+    pub fn write_path(&mut self, content: &String) {
+        let style = self.style.as_ref().and_then(|s| s.path_color);
+        self.write(content, style).unwrap();
+    }
+
+    pub fn write_separator(&mut self, content: &String) {
+        let style = self.style.as_ref().and_then(|s| s.separator_color);
+        self.write(content, style).unwrap();
+    }
+
+    pub fn write_raw(&mut self, content: &str) {
+        write!(self.writer, "{}", content).unwrap();
+    }
+
+    /// Flush written content, ie when results are streamed
+    pub fn flush(&mut self) {
+        self.writer.flush().unwrap();
+    }
+
+    fn write(&mut self, content: &String, style: Option<TextStyle>) -> Result<()> {
+        match style {
+            Some(style) if self.color => {
+                write!(self.writer, "{}", style.paint(content)).unwrap();
+            }
+            _ => {
+                write!(self.writer, "{}", content).unwrap();
+            }
         }
         Ok(())
     }
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Result};
+
+/// Prefix of named patterns, ie `@email`
+pub const PATTERN_PREFIX: char = '@';
+
+pub const EMAIL: &str = r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b";
+pub const IPV4: &str =
+    r"\b(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b";
+pub const IPV6: &str = r"(?i)\b(?:[0-9a-f]{1,4}:){7}[0-9a-f]{1,4}\b|\b(?:[0-9a-f]{1,4}:){1,7}:(?:[0-9a-f]{1,4}(?::[0-9a-f]{1,4}){0,6}\b)?";
+pub const UUID: &str = r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b";
+pub const ISO8601: &str =
+    r"\b\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2}(?:[.,]\d+)?)?(?:Z|[+-]\d{2}:?\d{2})?)?\b";
+pub const AWS_KEY: &str = r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b";
+pub const JWT: &str = r"\beyJ[\w-]+\.[\w-]+\.[\w-]+";
+
+/// Built-in named patterns
+pub const BUILTIN_PATTERNS: [(&str, &str); 7] = [
+    ("email", EMAIL),
+    ("ipv4", IPV4),
+    ("ipv6", IPV6),
+    ("uuid", UUID),
+    ("iso8601", ISO8601),
+    ("aws-key", AWS_KEY),
+    ("jwt", JWT),
+];
+
+/// Expand named pattern, ie `@email`, other patterns are returned as is
+///
+/// User aliases take precedence over built-in patterns and can refer to them. Unknown
+/// names, ie `@admin`, are searched literally, `\@email` searches `@email` literally.
+pub fn expand_pattern(pattern: &str, aliases: &BTreeMap<String, String>) -> Result<String> {
+    let Some(name) = pattern.strip_prefix(PATTERN_PREFIX) else {
+        return Ok(pattern.to_string());
+    };
+
+    if let Some(alias) = aliases.get(name) {
+        return match alias.strip_prefix(PATTERN_PREFIX) {
+            Some(builtin) => get_builtin_pattern(builtin)
+                .map(|p| p.to_string())
+                .ok_or_else(|| unknown_pattern(alias)),
+            None => Ok(alias.to_string()),
+        };
+    }
+
+    Ok(get_builtin_pattern(name).unwrap_or(pattern).to_string())
+}
+
+pub fn get_builtin_pattern(name: &str) -> Option<&'static str> {
+    BUILTIN_PATTERNS
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, pattern)| *pattern)
+}
+
+fn unknown_pattern(pattern: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("Unknown pattern {}", pattern),
+    )
+}
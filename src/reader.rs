@@ -1,18 +1,239 @@
 use std::fs::File;
-use std::io::{stdin, BufRead, BufReader, Result};
-use std::path::PathBuf;
+use std::io::{stdin, BufRead, BufReader, Cursor, Read, Result};
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
+use encoding_rs::Encoding;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use memmap2::Mmap;
 
-pub fn get_reader(input_path: Option<PathBuf>) -> Result<Box<dyn BufRead>> {
-    let reader: Box<dyn BufRead> = match &input_path {
-        None => Box::new(BufReader::new(stdin())),
-        Some(file_path) => Box::new(BufReader::new(
-            File::open(file_path)
-                .with_context(|| format!("Error reading file {}", file_path.display()))
-                .unwrap(),
-        )),
+use okolib::archive::{Archive, MEMBER_SEPARATOR};
+use okolib::binary::{is_binary, BinaryMode, LossyReader, BLOCK_SIZE};
+use okolib::compression::{Compression, MAGIC_SIZE};
+use okolib::encoding::{decode, UTF16BE_BOM, UTF16LE_BOM, UTF8_BOM};
+
+/// Path of stdin in messages
+const STDIN_PATH: &str = "(standard input)";
+
+/// Minimal size of files to be memory-mapped
+const MMAP_MIN_SIZE: u64 = 1024 * 1024;
+
+/// Content of input, large files are memory-mapped
+pub enum Content<'a> {
+    Reader(&'a mut dyn BufRead),
+    Mapped(&'a [u8]),
+}
+
+/// Input files, or stdin if there are none, with their handling
+pub struct Inputs {
+    pub paths: Vec<PathBuf>,
+    pub binary: BinaryMode,
+    pub encoding: Option<&'static Encoding>,
+    pub decompress: bool,
+    // Archive members matching any glob are read, all if there are none
+    pub globs: Option<GlobSet>,
+    // Large regular files are memory-mapped
+    pub mmap: bool,
+}
+
+impl Inputs {
+    pub fn new(
+        paths: Vec<PathBuf>,
+        binary: BinaryMode,
+        encoding: Option<&'static Encoding>,
+        decompress: bool,
+    ) -> Self {
+        Self {
+            paths,
+            binary,
+            encoding,
+            decompress,
+            globs: None,
+            mmap: true,
+        }
+    }
+
+    /// Set globs of archive member paths, ie `logs/*.log` or `**/*.txt`
+    pub fn set_globs(&mut self, globs: &[String]) {
+        if globs.is_empty() {
+            self.globs = None;
+            return;
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for glob in globs {
+            builder.add(
+                GlobBuilder::new(glob)
+                    .literal_separator(true)
+                    .build()
+                    .with_context(|| format!("Invalid glob {}", glob))
+                    .unwrap(),
+            );
+        }
+        self.globs = Some(builder.build().unwrap());
+    }
+
+    /// Check whether results should be tagged with paths, ie there are multiple inputs
+    pub fn has_multiple(&self) -> bool {
+        self.paths.len() > 1
+            || self
+                .paths
+                .iter()
+                .any(|path| Archive::from_path(path).is_some())
+    }
+
+    /// Check whether file can be memory-mapped, ie it needs no decoding
+    fn is_mappable(&self, file_path: &Path) -> bool {
+        self.mmap
+            && self.encoding.is_none()
+            && !self.decompress
+            && Compression::from_path(file_path).is_none()
+    }
+
+    fn is_member_selected(&self, member_path: &str) -> bool {
+        match &self.globs {
+            Some(globs) => globs.is_match(member_path),
+            None => true,
+        }
+    }
+}
+
+/// Read stdin or every input file, descending into archive members
+///
+/// Callback gets path of the input, ie `bundle.zip!/logs/app.log` for archive members,
+/// its content and whether content looks binary.
+pub fn read_inputs<F>(inputs: &Inputs, mut callback: F) -> Result<()>
+where
+    F: FnMut(String, Content, bool) -> Result<()>,
+{
+    if inputs.paths.is_empty() {
+        let (mut reader, binary) =
+            get_input_reader(Box::new(stdin()), None, inputs.encoding, inputs.decompress)?;
+        return callback(STDIN_PATH.to_string(), Content::Reader(&mut reader), binary);
+    }
+
+    for input_path in &inputs.paths {
+        let path = input_path.display().to_string();
+        let file = open_file(input_path);
+
+        match Archive::from_path(input_path) {
+            Some(archive) => archive.read_members(file, |member_path, member| {
+                if !inputs.is_member_selected(member_path) {
+                    return Ok(());
+                }
+                let (mut reader, binary) = get_input_reader(
+                    Box::new(member),
+                    Some(Path::new(member_path)),
+                    inputs.encoding,
+                    inputs.decompress,
+                )?;
+                callback(
+                    format!("{}{}{}", path, MEMBER_SEPARATOR, member_path),
+                    Content::Reader(&mut reader),
+                    binary,
+                )
+            })?,
+            None => {
+                if inputs.is_mappable(input_path) {
+                    if let Some(mmap) = map_file(&file)? {
+                        // UTF-16 is decoded by reader
+                        if !mmap.starts_with(UTF16LE_BOM) && !mmap.starts_with(UTF16BE_BOM) {
+                            let content = mmap.strip_prefix(UTF8_BOM).unwrap_or(&mmap);
+                            callback(path, Content::Mapped(content), is_binary(content))?;
+                            continue;
+                        }
+                    }
+                }
+
+                let (mut reader, binary) = get_input_reader(
+                    Box::new(file),
+                    Some(input_path),
+                    inputs.encoding,
+                    inputs.decompress,
+                )?;
+                callback(path, Content::Reader(&mut reader), binary)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn get_reader(
+    input_path: Option<PathBuf>,
+    encoding: Option<&'static Encoding>,
+) -> Result<Box<dyn BufRead>> {
+    let input: Box<dyn Read> = match &input_path {
+        None => Box::new(stdin()),
+        Some(file_path) => Box::new(open_file(file_path)),
     };
 
-    Ok(reader)
+    Ok(get_input_reader(input, input_path.as_deref(), encoding, false)?.0)
+}
+
+/// Get reader decoding content into UTF-8 and whether content looks binary
+///
+/// Files with known extensions are decompressed, any input is decompressed by its magic
+/// bytes if requested. Invalid UTF-8 is decoded lossily.
+fn get_input_reader<'a>(
+    input: Box<dyn Read + 'a>,
+    input_path: Option<&Path>,
+    encoding: Option<&'static Encoding>,
+    decompress: bool,
+) -> Result<(Box<dyn BufRead + 'a>, bool)> {
+    let decompress =
+        decompress || input_path.is_some_and(|path| Compression::from_path(path).is_some());
+    let input = if decompress {
+        decompress_input(input)?
+    } else {
+        input
+    };
+
+    let mut decoder = decode(input, encoding);
+
+    // Decoder can return less than requested, so first block is read until it is full
+    let mut block = vec![];
+    decoder
+        .by_ref()
+        .take(BLOCK_SIZE as u64)
+        .read_to_end(&mut block)?;
+    let binary = is_binary(&block);
+
+    let reader = BufReader::new(Cursor::new(block).chain(decoder));
+    Ok((Box::new(LossyReader::new(reader)), binary))
+}
+
+/// Memory-map the file if it is large regular file
+fn map_file(file: &File) -> Result<Option<Mmap>> {
+    let metadata = file.metadata()?;
+    if !metadata.is_file() || metadata.len() < MMAP_MIN_SIZE {
+        return Ok(None);
+    }
+
+    // SAFETY: File is only read, it can be truncated by other process meanwhile, which is
+    // why memory-mapping can be disabled.
+    let mmap = unsafe { Mmap::map(file)? };
+    Ok(Some(mmap))
+}
+
+fn open_file(file_path: &Path) -> File {
+    File::open(file_path)
+        .with_context(|| format!("Error reading file {}", file_path.display()))
+        .unwrap()
+}
+
+/// Get reader decompressing input if it starts with known magic bytes
+fn decompress_input<'a>(mut input: Box<dyn Read + 'a>) -> Result<Box<dyn Read + 'a>> {
+    let mut header = vec![];
+    input
+        .by_ref()
+        .take(MAGIC_SIZE as u64)
+        .read_to_end(&mut header)?;
+    let compression = Compression::detect(&header);
+
+    let input = Box::new(Cursor::new(header).chain(input));
+    match compression {
+        Some(compression) => compression.decompress(input),
+        None => Ok(input),
+    }
 }
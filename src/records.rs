@@ -0,0 +1,115 @@
+use std::io::{BufRead, Result};
+
+use regex::Regex;
+
+/// Separator of records, ie multi-line log entries
+pub enum RecordSeparator {
+    // Record starts at every line matching the pattern
+    Start(Regex),
+    // Records are separated by one or more blank lines
+    Blank,
+    // Records are separated by the text, ie NUL
+    Text(String),
+}
+
+impl RecordSeparator {
+    /// Get separator from `blank`, `nul` or any other text
+    ///
+    /// Escapes `\n`, `\t` and `\0` are supported in text.
+    pub fn parse(separator: &str) -> Self {
+        match separator {
+            "blank" => RecordSeparator::Blank,
+            "nul" => RecordSeparator::Text("\0".to_string()),
+            _ => RecordSeparator::Text(
+                separator
+                    .replace("\\n", "\n")
+                    .replace("\\t", "\t")
+                    .replace("\\0", "\0"),
+            ),
+        }
+    }
+
+    /// Check whether records are separated by NUL, ie content is binary
+    pub fn is_nul(&self) -> bool {
+        matches!(self, RecordSeparator::Text(text) if text.contains('\0'))
+    }
+}
+
+/// Record with its first line number
+pub struct Record {
+    pub line: usize,
+    pub content: String,
+}
+
+/// Read records
+///
+/// Records don't contain separators nor trailing line breaks, empty records are skipped.
+pub fn read_records<R: BufRead>(
+    reader: &mut R,
+    separator: &RecordSeparator,
+) -> Result<Vec<Record>> {
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    // Start and end positions of records
+    let mut bounds: Vec<(usize, usize)> = vec![];
+    match separator {
+        RecordSeparator::Start(pattern) => {
+            let mut start_pos = 0usize;
+            let mut line_start_pos = 0usize;
+            for line in content.split_inclusive('\n') {
+                if line_start_pos > 0 && pattern.is_match(line.trim_end_matches(['\n', '\r'])) {
+                    bounds.push((start_pos, line_start_pos));
+                    start_pos = line_start_pos;
+                }
+                line_start_pos += line.len();
+            }
+            bounds.push((start_pos, content.len()));
+        }
+        RecordSeparator::Blank => {
+            let mut start_pos: Option<usize> = None;
+            let mut line_start_pos = 0usize;
+            for line in content.split_inclusive('\n') {
+                let blank = line.trim().is_empty();
+                match (start_pos, blank) {
+                    (None, false) => start_pos = Some(line_start_pos),
+                    (Some(start), true) => {
+                        bounds.push((start, line_start_pos));
+                        start_pos = None;
+                    }
+                    _ => {}
+                }
+                line_start_pos += line.len();
+            }
+            if let Some(start) = start_pos {
+                bounds.push((start, content.len()));
+            }
+        }
+        RecordSeparator::Text(text) => {
+            let mut start_pos = 0usize;
+            for (pos, _) in content.match_indices(text.as_str()) {
+                bounds.push((start_pos, pos));
+                start_pos = pos + text.len();
+            }
+            bounds.push((start_pos, content.len()));
+        }
+    }
+
+    let mut records: Vec<Record> = vec![];
+    let mut line = 1usize;
+    let mut prev_start_pos = 0usize;
+    for (start_pos, end_pos) in bounds {
+        line += content[prev_start_pos..start_pos].matches('\n').count();
+        prev_start_pos = start_pos;
+
+        let record = content[start_pos..end_pos].trim_end_matches(['\n', '\r']);
+        if !record.is_empty() {
+            records.push(Record {
+                line,
+                content: record.to_string(),
+            });
+        }
+    }
+
+    Ok(records)
+}
@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, Result};
+
+use clap::ValueEnum;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use crate::patterns::{AWS_KEY, EMAIL, IPV4, IPV6, JWT};
+
+/// Category of user patterns
+pub const CUSTOM_CATEGORY: &str = "custom";
+
+/// Built-in detector
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
+pub enum Detector {
+    Email,
+    Ipv4,
+    Ipv6,
+    Token,
+    Card,
+}
+
+impl Detector {
+    pub fn all() -> Vec<Detector> {
+        vec![
+            Detector::Token,
+            Detector::Card,
+            Detector::Email,
+            Detector::Ipv6,
+            Detector::Ipv4,
+        ]
+    }
+
+    pub fn category(&self) -> &'static str {
+        match self {
+            Detector::Email => "email",
+            Detector::Ipv4 => "ipv4",
+            Detector::Ipv6 => "ipv6",
+            Detector::Token => "token",
+            Detector::Card => "card",
+        }
+    }
+
+    pub fn pattern(&self) -> String {
+        match self {
+            Detector::Email => EMAIL.to_string(),
+            Detector::Ipv4 => IPV4.to_string(),
+            Detector::Ipv6 => IPV6.to_string(),
+            Detector::Token => [
+                r"(?i:\bbearer\s+)[\w.~+/-]+=*",
+                AWS_KEY,
+                JWT,
+                r"\b(?:gh[pousr]_[A-Za-z0-9]{36,}|xox[abprs]-[A-Za-z0-9-]{10,}|sk-[A-Za-z0-9_-]{20,})",
+            ]
+            .join("|"),
+            Detector::Card => r"\b\d(?:[ -]?\d){12,18}\b".to_string(),
+        }
+    }
+
+    /// Check whether the match is really of the category, ie card numbers pass Luhn check
+    fn is_valid(&self, content: &str) -> bool {
+        match self {
+            Detector::Card => luhn(content),
+            _ => true,
+        }
+    }
+}
+
+/// Mask of redacted content
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
+pub enum Mask {
+    // Replace with fixed text
+    Fixed,
+    // Keep last 4 characters
+    Partial,
+    // Replace with stable hash, so equal values stay comparable
+    Hash,
+}
+
+/// Redaction results with number of redactions per category
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct RedactResults {
+    pub content: String,
+    pub counts: BTreeMap<String, usize>,
+}
+
+pub struct Redactor {
+    mask: Mask,
+    mask_text: String,
+    detectors: Vec<(String, Option<Detector>, Regex)>,
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl Redactor {
+    pub fn new(mask: Mask, mask_text: String) -> Self {
+        Self {
+            mask,
+            mask_text,
+            detectors: vec![],
+            counts: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_detector(&mut self, detector: Detector) {
+        let pattern = Regex::new(&detector.pattern()).unwrap();
+        self.detectors
+            .push((detector.category().to_string(), Some(detector), pattern));
+    }
+
+    pub fn add_pattern(&mut self, pattern: &str, ignore_case: &bool) {
+        let pattern = RegexBuilder::new(pattern)
+            .case_insensitive(*ignore_case)
+            .build()
+            .unwrap();
+        self.detectors
+            .push((CUSTOM_CATEGORY.to_string(), None, pattern));
+    }
+
+    /// Redact content
+    ///
+    /// When matches of detectors overlap, the one starting first is redacted.
+    pub fn redact(&mut self, content: &str) -> String {
+        let mut matches: Vec<(usize, usize, usize)> = vec![];
+        for (idx, (_, detector, pattern)) in self.detectors.iter().enumerate() {
+            for m in pattern.find_iter(content) {
+                if m.is_empty() || detector.as_ref().is_some_and(|d| !d.is_valid(m.as_str())) {
+                    continue;
+                }
+                matches.push((m.start(), idx, m.end()));
+            }
+        }
+        matches.sort();
+
+        let mut result = String::with_capacity(content.len());
+        let mut prev_end_pos = 0usize;
+        for (start_pos, idx, end_pos) in matches {
+            if start_pos < prev_end_pos {
+                continue;
+            }
+            let category = &self.detectors[idx].0;
+            result.push_str(&content[prev_end_pos..start_pos]);
+            result.push_str(&self.mask(category, &content[start_pos..end_pos]));
+            *self.counts.entry(category.clone()).or_default() += 1;
+            prev_end_pos = end_pos;
+        }
+        result.push_str(&content[prev_end_pos..]);
+        result
+    }
+
+    /// Redact lines, passing each redacted line with its original ending to the callback
+    pub fn redact_lines<R: BufRead, F: FnMut(&str)>(
+        &mut self,
+        reader: &mut R,
+        mut callback: F,
+    ) -> Result<()> {
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            let content = line.trim_end_matches(['\n', '\r']);
+            let line_ending = &line[content.len()..];
+            let mut redacted = self.redact(content);
+            redacted.push_str(line_ending);
+            callback(&redacted);
+            line.clear();
+        }
+        Ok(())
+    }
+
+    fn mask(&self, category: &str, content: &str) -> String {
+        match self.mask {
+            Mask::Fixed => self.mask_text.clone(),
+            Mask::Partial => partial_mask(content),
+            Mask::Hash => format!("[{}:{:08x}]", category, fnv1a(content) as u32),
+        }
+    }
+}
+
+/// Redact all content of the reader
+pub fn redact<R: BufRead>(reader: &mut R, redactor: &mut Redactor) -> Result<RedactResults> {
+    let mut content = String::new();
+    redactor.redact_lines(reader, |line| content.push_str(line))?;
+
+    Ok(RedactResults {
+        content,
+        counts: redactor.counts.clone(),
+    })
+}
+
+/// Mask all but last 4 characters, short content is masked completely
+fn partial_mask(content: &str) -> String {
+    let len = content.chars().count();
+    let keep = if len > 8 { 4 } else { 0 };
+    content
+        .chars()
+        .enumerate()
+        .map(|(idx, c)| if idx < len - keep { '*' } else { c })
+        .collect()
+}
+
+/// FNV-1a hash, stable across runs and platforms
+fn fnv1a(content: &str) -> u64 {
+    content.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn luhn(content: &str) -> bool {
+    let digits: Vec<u32> = content.chars().filter_map(|c| c.to_digit(10)).collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(idx, digit)| match (idx % 2, digit * 2) {
+            (0, _) => *digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
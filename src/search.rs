@@ -1,29 +1,349 @@
-use std::io::{BufRead, Result};
+use std::io::{BufRead, Error, ErrorKind, Result};
 
-use regex::RegexBuilder;
+use memchr::{memchr, memchr_iter, memrchr};
+use regex::bytes::{Match as BytesMatch, Regex as BytesRegex, RegexBuilder as BytesRegexBuilder};
+use regex::{Captures, Regex, RegexBuilder};
 
-use crate::search_results::{Match, SearchResult, SearchResults};
+use crate::count_results::{CountResults, PatternCount};
+use crate::records::{read_records, RecordSeparator};
+use crate::search_results::{Group, Location, Match, SearchResult, SearchResults};
 
 /// Search
-pub fn search<'a, R: BufRead>(
+pub fn search<R: BufRead>(
     reader: &mut R,
     pattern: &str,
     ignore_case: &bool,
 ) -> Result<SearchResults> {
-    let target_pattern = RegexBuilder::new(&pattern)
-        .case_insensitive(*ignore_case)
-        .build();
-
-    let mut results = SearchResults::new(pattern.to_string());
-    for (idx, line) in reader.by_ref().lines().enumerate() {
-        let content = line?;
-        let mut result = SearchResult::new(idx + 1, content.clone());
-        let mut matches: Vec<(Option<usize>, Option<usize>)> = vec![];
-        for m in target_pattern.as_ref().unwrap().find_iter(content.as_str()) {
-            matches.push((Some(m.start()), Some(m.end())));
-            result.add_match(Match::new(m.as_str().to_string(), m.start(), m.end()));
-        }
-        if matches.len() > 0 {
+    search_patterns(reader, &[pattern.to_string()], ignore_case, &false)
+}
+
+/// Search multiple patterns
+///
+/// Matches are reported with index of the pattern and capture groups. When matches of
+/// different patterns overlap, the one starting first is kept, unless overlapping matches
+/// are requested.
+///
+/// Lines are read into a reused buffer and matched as bytes, only matching lines are
+/// decoded, lossily.
+pub fn search_patterns<R: BufRead>(
+    reader: &mut R,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+) -> Result<SearchResults> {
+    let target_patterns = build_patterns(patterns, ignore_case);
+    let bytes_patterns = build_bytes_patterns(patterns, ignore_case);
+
+    let mut results = SearchResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns.to_vec();
+    }
+    let mut buffer = vec![];
+    let mut line = 0usize;
+    while let Some(line_content) = read_line(reader, &mut buffer)? {
+        line += 1;
+        search_line(
+            &target_patterns,
+            &bytes_patterns,
+            line,
+            line_content,
+            overlapping,
+            &mut results,
+        );
+    }
+
+    Ok(results)
+}
+
+/// Search line without line ending, adding its result if any pattern matches
+fn search_line(
+    target_patterns: &[Regex],
+    bytes_patterns: &[BytesRegex],
+    line: usize,
+    line_content: &[u8],
+    overlapping: &bool,
+    results: &mut SearchResults,
+) {
+    if !bytes_patterns
+        .iter()
+        .any(|pattern| pattern.is_match(line_content))
+    {
+        return;
+    }
+
+    let content = String::from_utf8_lossy(line_content).into_owned();
+    let matches = find_matches(target_patterns, &content, *overlapping);
+    if !matches.is_empty() {
+        let mut result = SearchResult::new(line, content);
+        for m in matches {
+            result.add_match(m);
+        }
+        results.add_result(result);
+    }
+}
+
+/// Search multiple patterns in whole content, ie memory-mapped file
+///
+/// Results are same as in `search_patterns`. Patterns are found in whole content as bytes,
+/// with `^`/`$` matching at line boundaries, and only lines of found matches are searched
+/// again and decoded, lossily. Patterns anchored at content boundaries, ie `\A` or `(?-m)^`,
+/// and content with carriage returns inside lines are matched line by line instead.
+pub fn search_bytes(
+    content: &[u8],
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+) -> SearchResults {
+    let target_patterns = build_patterns(patterns, ignore_case);
+    let bytes_patterns = build_bytes_patterns(patterns, ignore_case);
+
+    let mut results = SearchResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns.to_vec();
+    }
+
+    let content_patterns: Vec<BytesRegex> = if has_content_anchors(patterns) || has_lone_cr(content)
+    {
+        vec![]
+    } else {
+        patterns
+            .iter()
+            .map(|pattern| {
+                BytesRegexBuilder::new(pattern)
+                    .case_insensitive(*ignore_case)
+                    .multi_line(true)
+                    .crlf(true)
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    };
+    if content_patterns.is_empty() {
+        for (line, line_content) in split_lines(content).enumerate() {
+            search_line(
+                &target_patterns,
+                &bytes_patterns,
+                line + 1,
+                line_content,
+                overlapping,
+                &mut results,
+            );
+        }
+        return results;
+    }
+
+    let find = |pattern: &BytesRegex, pos: usize| {
+        pattern
+            .find_at(content, pos)
+            .map_or(usize::MAX, |m| m.start())
+    };
+    // Start of next match of each pattern
+    let mut starts: Vec<usize> = content_patterns.iter().map(|p| find(p, 0)).collect();
+    // Final new line ends the last line, so there is no empty line after it to match
+    let last_pos = match content.last() {
+        Some(b'\n') => content.len() - 1,
+        Some(_) => content.len(),
+        None => return results,
+    };
+    let mut line = 1usize;
+    let mut line_pos = 0usize;
+    loop {
+        let start = *starts.iter().min().unwrap();
+        if start > last_pos {
+            break;
+        }
+
+        let line_start = memrchr(b'\n', &content[..start]).map_or(0, |idx| idx + 1);
+        let line_end = memchr(b'\n', &content[start..]).map_or(content.len(), |idx| start + idx);
+        line += memchr_iter(b'\n', &content[line_pos..line_start]).count();
+        line_pos = line_start;
+
+        let line_content = &content[line_start..line_end];
+        search_line(
+            &target_patterns,
+            &bytes_patterns,
+            line,
+            line_content.strip_suffix(b"\r").unwrap_or(line_content),
+            overlapping,
+            &mut results,
+        );
+
+        // Continue on next line
+        let pos = line_end + 1;
+        if pos >= content.len() {
+            break;
+        }
+        for (pattern, start) in content_patterns.iter().zip(starts.iter_mut()) {
+            if *start < pos {
+                *start = find(pattern, pos);
+            }
+        }
+    }
+
+    results
+}
+
+/// Split content into lines without line endings
+fn split_lines(content: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let lines = content.strip_suffix(b"\n").unwrap_or(content);
+    (!content.is_empty())
+        .then(|| lines.split(|byte| *byte == b'\n'))
+        .into_iter()
+        .flatten()
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// Whether content has carriage return not ending line, which dot matches only in lines
+fn has_lone_cr(content: &[u8]) -> bool {
+    memchr_iter(b'\r', content).any(|idx| content.get(idx + 1) != Some(&b'\n'))
+}
+
+/// Whether any pattern is anchored at content boundaries, ie `\A`, `\z` or `(?-m)$`
+fn has_content_anchors(patterns: &[String]) -> bool {
+    let anchors = Regex::new(r"\\[Az]|\(\?[a-zA-Z]*-[a-zA-Z]*m").unwrap();
+    patterns.iter().any(|pattern| anchors.is_match(pattern))
+}
+
+/// Search multiple patterns in whole content, so matches can span lines
+///
+/// Dot matches new line and `^`/`$` match at line boundaries. Each result contains all
+/// lines spanned by its matches, starting at the result line, and every match has its
+/// location.
+pub fn search_multiline<R: BufRead>(
+    reader: &mut R,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+) -> Result<SearchResults> {
+    let target_patterns: Vec<Regex> = patterns
+        .iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(*ignore_case)
+                .dot_matches_new_line(true)
+                .multi_line(true)
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    // Start positions of lines
+    let line_starts: Vec<usize> = [0]
+        .into_iter()
+        .chain(content.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect();
+    let get_line = |pos: usize| line_starts.partition_point(|start| *start <= pos) - 1;
+
+    let mut matches = find_matches(&target_patterns, &content, *overlapping);
+
+    // Final new line ends the last line, so there is no empty line after it to match
+    if content.ends_with('\n') {
+        matches.retain(|m| m.start_pos < content.len());
+    }
+
+    let mut results = SearchResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns.to_vec();
+    }
+
+    // Group matches spanning common lines into results
+    let mut groups: Vec<(usize, usize, Vec<Match>)> = vec![];
+    for mut m in matches {
+        // Match ending with new line ends on its line, trimmed as result content is
+        if let Some(content) = m.content.strip_suffix('\n') {
+            let len = content.strip_suffix('\r').unwrap_or(content).len();
+            m.content.truncate(len);
+            m.end_pos = m.start_pos + len;
+            for g in &mut m.groups {
+                g.end_pos = g.end_pos.min(m.end_pos).max(g.start_pos);
+                g.content.truncate(g.end_pos - g.start_pos);
+            }
+        }
+
+        let start_line = get_line(m.start_pos);
+        let end_line = get_line(m.end_pos.saturating_sub(1).max(m.start_pos));
+        m.location = Some(Location::new(
+            start_line + 1,
+            content[line_starts[start_line]..m.start_pos]
+                .chars()
+                .count()
+                + 1,
+            get_line(m.end_pos) + 1,
+            content[line_starts[get_line(m.end_pos)]..m.end_pos]
+                .chars()
+                .count()
+                + 1,
+        ));
+
+        match groups.last_mut() {
+            Some((_, last_line, group)) if start_line <= *last_line => {
+                *last_line = (*last_line).max(end_line);
+                group.push(m);
+            }
+            _ => groups.push((start_line, end_line, vec![m])),
+        }
+    }
+
+    for (start_line, end_line, group) in groups {
+        let start_pos = line_starts[start_line];
+        let end_pos = match line_starts.get(end_line + 1) {
+            Some(next_start) => next_start - 1,
+            None => content.len(),
+        };
+        let lines = content[start_pos..end_pos].trim_end_matches('\r');
+
+        let mut result = SearchResult::new(start_line + 1, lines.to_string());
+        for mut m in group {
+            // Positions relative to the result content
+            m.start_pos -= start_pos;
+            m.end_pos -= start_pos;
+            for g in &mut m.groups {
+                g.start_pos -= start_pos;
+                g.end_pos -= start_pos;
+            }
+            result.add_match(m);
+        }
+        results.add_result(result);
+    }
+
+    Ok(results)
+}
+
+/// Search multiple patterns in records rather than lines
+///
+/// Each result contains the whole record, starting at its first line, and every match
+/// has its location.
+pub fn search_records<R: BufRead>(
+    reader: &mut R,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+    separator: &RecordSeparator,
+) -> Result<SearchResults> {
+    let target_patterns = build_patterns(patterns, ignore_case);
+
+    let mut results = SearchResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns.to_vec();
+    }
+    for record in read_records(reader, separator)? {
+        let mut matches = find_matches(&target_patterns, &record.content, *overlapping);
+        if !matches.is_empty() {
+            for m in &mut matches {
+                m.location = Some(get_location(
+                    &record.content,
+                    record.line,
+                    m.start_pos,
+                    m.end_pos,
+                ));
+            }
+            let mut result = SearchResult::new(record.line, record.content);
+            for m in matches {
+                result.add_match(m);
+            }
             results.add_result(result);
         }
     }
@@ -33,18 +353,389 @@ pub fn search<'a, R: BufRead>(
 
 /// Count
 pub fn count<R: BufRead>(reader: &mut R, pattern: &str, ignore_case: &bool) -> Result<usize> {
-    let target_pattern = RegexBuilder::new(&pattern)
+    count_patterns(reader, &[pattern.to_string()], ignore_case)
+}
+
+/// Count multiple patterns
+///
+/// Overlapping matches of different patterns are counted once, same as in search.
+pub fn count_patterns<R: BufRead>(
+    reader: &mut R,
+    patterns: &[String],
+    ignore_case: &bool,
+) -> Result<usize> {
+    Ok(count_breakdown(reader, patterns, ignore_case, &false, &false)?.total)
+}
+
+/// Count multiple patterns with breakdown per pattern and optionally per line
+///
+/// Overlapping matches are counted same as in search. Lines are read into a reused
+/// buffer and matched as bytes, so nothing is allocated per line.
+pub fn count_breakdown<R: BufRead>(
+    reader: &mut R,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+    per_line: &bool,
+) -> Result<CountResults> {
+    let bytes_patterns = build_bytes_patterns(patterns, ignore_case);
+
+    let mut results = CountResults::new(patterns.join("|"));
+    if patterns.len() > 1 {
+        results.patterns = patterns
+            .iter()
+            .map(|pattern| PatternCount::new(pattern.to_string(), 0))
+            .collect();
+    }
+    let mut buffer = vec![];
+    let mut spans = vec![];
+    let mut pattern_counts = vec![0usize; patterns.len()];
+    let mut line = 0usize;
+    while let Some(line_content) = read_line(reader, &mut buffer)? {
+        line += 1;
+        pattern_counts.fill(0);
+        count_matches(
+            &bytes_patterns,
+            line_content,
+            *overlapping,
+            &mut spans,
+            &mut pattern_counts,
+        );
+        results.add_line(line, &pattern_counts, *per_line);
+    }
+
+    Ok(results)
+}
+
+/// Count multiple patterns in records with breakdown per pattern and optionally per record
+///
+/// Records are counted as lines, starting at their first line.
+pub fn count_records<R: BufRead>(
+    reader: &mut R,
+    patterns: &[String],
+    ignore_case: &bool,
+    overlapping: &bool,
+    per_line: &bool,
+    separator: &RecordSeparator,
+) -> Result<CountResults> {
+    let bytes_patterns = build_bytes_patterns(patterns, ignore_case);
+
+    let mut results = CountResults::new(patterns.join("|"));
+    results.records = true;
+    if patterns.len() > 1 {
+        results.patterns = patterns
+            .iter()
+            .map(|pattern| PatternCount::new(pattern.to_string(), 0))
+            .collect();
+    }
+    let mut spans = vec![];
+    let mut pattern_counts = vec![0usize; patterns.len()];
+    for record in read_records(reader, separator)? {
+        pattern_counts.fill(0);
+        count_matches(
+            &bytes_patterns,
+            record.content.as_bytes(),
+            *overlapping,
+            &mut spans,
+            &mut pattern_counts,
+        );
+        results.add_line(record.line, &pattern_counts, *per_line);
+    }
+
+    Ok(results)
+}
+
+/// Replace
+///
+/// Every match in a line is replaced, replacement can reference capture groups
+/// using `$1` or `${name}`. Content is returned with original line endings.
+pub fn replace<R: BufRead>(
+    reader: &mut R,
+    pattern: &str,
+    replacement: &str,
+    ignore_case: &bool,
+) -> Result<String> {
+    let target_pattern = RegexBuilder::new(pattern)
         .case_insensitive(*ignore_case)
-        .build();
-
-    let mut results = 0;
-    for line in reader.by_ref().lines() {
-        results += target_pattern
-            .as_ref()
-            .unwrap()
-            .find_iter(line?.as_str())
-            .count();
+        .build()
+        .unwrap();
+
+    let mut results = String::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let line_ending = &line[content.len()..];
+        results.push_str(&target_pattern.replace_all(content, replacement));
+        results.push_str(line_ending);
+        line.clear();
     }
 
     Ok(results)
 }
+
+/// Replace in content as bytes, ie file edited in place
+///
+/// Same as `replace`, but invalid UTF-8 is never decoded, so bytes outside of matches
+/// are kept as they are.
+pub fn replace_bytes(
+    content: &[u8],
+    pattern: &str,
+    replacement: &str,
+    ignore_case: &bool,
+) -> Vec<u8> {
+    let target_pattern = BytesRegexBuilder::new(pattern)
+        .case_insensitive(*ignore_case)
+        .build()
+        .unwrap();
+
+    let mut results = Vec::with_capacity(content.len());
+    for line in content.split_inclusive(|b| *b == b'\n') {
+        let line_content_len = line
+            .iter()
+            .rposition(|b| *b != b'\n' && *b != b'\r')
+            .map_or(0, |idx| idx + 1);
+        let (line_content, line_ending) = line.split_at(line_content_len);
+        results
+            .extend_from_slice(&target_pattern.replace_all(line_content, replacement.as_bytes()));
+        results.extend_from_slice(line_ending);
+    }
+
+    results
+}
+
+/// Check that capture group, given by index or name, exists in any of the patterns
+pub fn check_group(patterns: &[String], group: &str) -> Result<()> {
+    let index = group.parse::<usize>().ok();
+    let exists = patterns
+        .iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .any(|target_pattern| match index {
+            Some(index) => index < target_pattern.captures_len(),
+            None => target_pattern
+                .capture_names()
+                .any(|name| name == Some(group)),
+        });
+    if !exists {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Unknown capture group {}", group),
+        ));
+    }
+    Ok(())
+}
+
+fn build_patterns(patterns: &[String], ignore_case: &bool) -> Vec<Regex> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(*ignore_case)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn build_bytes_patterns(patterns: &[String], ignore_case: &bool) -> Vec<BytesRegex> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            BytesRegexBuilder::new(pattern)
+                .case_insensitive(*ignore_case)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Read next line into the buffer, returning it without line ending
+fn read_line<'a, R: BufRead>(reader: &mut R, buffer: &'a mut Vec<u8>) -> Result<Option<&'a [u8]>> {
+    buffer.clear();
+    if reader.read_until(b'\n', buffer)? == 0 {
+        return Ok(None);
+    }
+
+    let mut line = &buffer[..];
+    if let Some(content) = line.strip_suffix(b"\n") {
+        line = content.strip_suffix(b"\r").unwrap_or(content);
+    }
+    Ok(Some(line))
+}
+
+/// Count matches of all patterns in content per pattern, same as `find_matches`
+///
+/// Spans of matches are collected into reused buffer, only when they have to be ordered.
+fn count_matches(
+    bytes_patterns: &[BytesRegex],
+    content: &[u8],
+    overlapping: bool,
+    spans: &mut Vec<(usize, usize, usize)>,
+    pattern_counts: &mut [usize],
+) {
+    if bytes_patterns.len() == 1 && !overlapping {
+        pattern_counts[0] += bytes_patterns[0]
+            .find_iter(content)
+            .filter(|m| is_char_match(content, m))
+            .count();
+        return;
+    }
+
+    spans.clear();
+    for (pattern_idx, bytes_pattern) in bytes_patterns.iter().enumerate() {
+        if overlapping {
+            let mut start_pos = 0usize;
+            while let Some(m) = bytes_pattern.find_at(content, start_pos) {
+                if is_char_match(content, &m) {
+                    spans.push((m.start(), pattern_idx, m.end()));
+                }
+
+                // Next match starts after first character of this one
+                match content.get(m.start()) {
+                    Some(b) => start_pos = m.start() + get_char_len(*b),
+                    None => break,
+                }
+            }
+            continue;
+        }
+
+        for m in bytes_pattern.find_iter(content) {
+            if is_char_match(content, &m) {
+                spans.push((m.start(), pattern_idx, m.end()));
+            }
+        }
+    }
+
+    if bytes_patterns.len() > 1 {
+        spans.sort_unstable();
+        if !overlapping {
+            // Keep first match of overlapping matches
+            let mut prev_end_pos = 0usize;
+            spans.retain(|(start_pos, _, end_pos)| {
+                if *start_pos < prev_end_pos {
+                    return false;
+                }
+                prev_end_pos = *end_pos;
+                true
+            });
+        }
+    }
+
+    for (_, pattern_idx, _) in spans.iter() {
+        pattern_counts[*pattern_idx] += 1;
+    }
+}
+
+/// Check whether match doesn't split a character, ie empty match inside of it
+fn is_char_match(content: &[u8], m: &BytesMatch) -> bool {
+    !m.is_empty()
+        || content
+            .get(m.start())
+            .is_none_or(|b| !(0x80..=0xbf).contains(b))
+}
+
+/// Get length of UTF-8 character from its first byte
+fn get_char_len(first_byte: u8) -> usize {
+    match first_byte {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    }
+}
+
+/// Get location of the match in content starting at the line
+fn get_location(content: &str, line: usize, start_pos: usize, end_pos: usize) -> Location {
+    let get_line_column = |pos: usize| {
+        let before = &content[..pos];
+        let line_start_pos = before.rfind('\n').map_or(0, |idx| idx + 1);
+        (
+            line + before.matches('\n').count(),
+            before[line_start_pos..].chars().count() + 1,
+        )
+    };
+    let (start_line, start_column) = get_line_column(start_pos);
+    let (end_line, end_column) = get_line_column(end_pos);
+
+    Location::new(start_line, start_column, end_line, end_column)
+}
+
+/// Find matches of all patterns in content
+///
+/// With overlapping every starting position of a match is reported, otherwise matches
+/// don't overlap.
+fn find_matches(target_patterns: &[Regex], content: &str, overlapping: bool) -> Vec<Match> {
+    let mut matches: Vec<Match> = vec![];
+    for (pattern_idx, target_pattern) in target_patterns.iter().enumerate() {
+        if overlapping {
+            let mut start_pos = 0usize;
+            while let Some(captures) = target_pattern.captures_at(content, start_pos) {
+                let m = captures.get(0).unwrap();
+                matches.push(get_match(&captures, target_pattern, pattern_idx));
+
+                // Next match starts after first character of this one
+                match content[m.start()..].chars().next() {
+                    Some(c) => start_pos = m.start() + c.len_utf8(),
+                    None => break,
+                }
+            }
+            continue;
+        }
+
+        // Without capture groups
+        if target_pattern.captures_len() == 1 {
+            for m in target_pattern.find_iter(content) {
+                let mut new_match = Match::new(m.as_str().to_string(), m.start(), m.end());
+                new_match.pattern = pattern_idx;
+                matches.push(new_match);
+            }
+            continue;
+        }
+
+        for captures in target_pattern.captures_iter(content) {
+            matches.push(get_match(&captures, target_pattern, pattern_idx));
+        }
+    }
+
+    if target_patterns.len() > 1 {
+        matches.sort_by_key(|m| (m.start_pos, m.pattern));
+        if !overlapping {
+            // Keep first match of overlapping matches
+            let mut prev_end_pos = 0usize;
+            matches.retain(|m| {
+                if m.start_pos < prev_end_pos {
+                    return false;
+                }
+                prev_end_pos = m.end_pos;
+                true
+            });
+        }
+    }
+
+    matches
+}
+
+/// Get match with capture groups
+fn get_match(captures: &Captures, target_pattern: &Regex, pattern_idx: usize) -> Match {
+    let m = captures.get(0).unwrap();
+    let mut new_match = Match::new(m.as_str().to_string(), m.start(), m.end());
+    new_match.pattern = pattern_idx;
+
+    // Capture groups
+    for (group_idx, group) in captures.iter().enumerate().skip(1) {
+        if let Some(group) = group {
+            new_match.add_group(Group::new(
+                group_idx,
+                target_pattern
+                    .capture_names()
+                    .nth(group_idx)
+                    .flatten()
+                    .map(|name| name.to_string()),
+                group.as_str().to_string(),
+                group.start(),
+                group.end(),
+            ));
+        }
+    }
+
+    new_match
+}
@@ -3,6 +3,8 @@ use serde::Serialize;
 #[derive(Debug, Serialize)]
 pub struct SearchResults {
     pub pattern: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
     pub results: Vec<SearchResult>,
 }
 
@@ -10,6 +12,7 @@ impl SearchResults {
     pub fn new(pattern: String) -> Self {
         Self {
             pattern,
+            patterns: vec![],
             results: vec![],
         }
     }
@@ -17,33 +20,74 @@ impl SearchResults {
     pub fn add_result(&mut self, result: SearchResult) {
         self.results.push(result);
     }
+
+    /// Group results by file path, keeping input order
+    pub fn group_by_path(&self) -> Vec<(Option<&str>, Vec<&SearchResult>)> {
+        let mut groups: Vec<(Option<&str>, Vec<&SearchResult>)> = vec![];
+        for result in &self.results {
+            let path = result.path.as_deref();
+            match groups.iter_mut().find(|(p, _)| *p == path) {
+                Some((_, group)) => group.push(result),
+                None => groups.push((path, vec![result])),
+            }
+        }
+        groups
+    }
+
+    /// Extract matched content of each result
+    ///
+    /// Without group the whole matches are extracted, otherwise the capture group given by
+    /// index or name. Results without extracted values are removed.
+    pub fn extract(&mut self, group: Option<&str>) {
+        for result in &mut self.results {
+            result.extracted = result
+                .matches
+                .iter()
+                .filter_map(|m| match group {
+                    None => Some(m.content.clone()),
+                    Some(group) => m.get_group(group).map(|g| g.to_string()),
+                })
+                .collect();
+        }
+        self.results.retain(|result| !result.extracted.is_empty());
+    }
 }
 
 impl PartialEq for SearchResults {
     fn eq(&self, other: &Self) -> bool {
-        if self.pattern == other.pattern && self.results == other.results {
-            return true;
-        }
-        return false;
+        self.pattern == other.pattern
+            && self.patterns == other.patterns
+            && self.results == other.results
     }
 }
 
 #[derive(Debug, Serialize)]
 pub struct SearchResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
     pub line: usize,
     pub content: String,
     pub matches: Vec<Match>,
+    // Extracted matches or capture groups, see `SearchResults::extract`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extracted: Vec<String>,
 }
 
 impl SearchResult {
     pub fn new(line: usize, content: String) -> Self {
         Self {
+            path: None,
             line,
             content,
             matches: vec![],
+            extracted: vec![],
         }
     }
 
+    pub fn set_path(&mut self, path: String) {
+        self.path = Some(path);
+    }
+
     pub fn add_match(&mut self, m: Match) {
         self.matches.push(m);
     }
@@ -51,11 +95,11 @@ impl SearchResult {
 
 impl PartialEq for SearchResult {
     fn eq(&self, other: &Self) -> bool {
-        if self.line == other.line && self.content == other.content && self.matches == other.matches
-        {
-            return true;
-        }
-        return false;
+        self.path == other.path
+            && self.line == other.line
+            && self.content == other.content
+            && self.matches == other.matches
+            && self.extracted == other.extracted
     }
 }
 
@@ -64,6 +108,13 @@ pub struct Match {
     pub content: String,
     pub start_pos: usize,
     pub end_pos: usize,
+    #[serde(skip_serializing_if = "is_first_pattern")]
+    pub pattern: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<Group>,
+    // Location of match in multiline search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
 }
 
 impl Match {
@@ -72,18 +123,102 @@ impl Match {
             content,
             start_pos,
             end_pos,
+            pattern: 0,
+            groups: vec![],
+            location: None,
+        }
+    }
+
+    pub fn add_group(&mut self, group: Group) {
+        self.groups.push(group);
+    }
+
+    /// Get content of the capture group by index or name, index 0 is the whole match
+    pub fn get_group(&self, group: &str) -> Option<&str> {
+        if group == "0" {
+            return Some(&self.content);
         }
+        let index = group.parse::<usize>().ok();
+        self.groups
+            .iter()
+            .find(|g| Some(g.index) == index || g.name.as_deref() == Some(group))
+            .map(|g| g.content.as_str())
     }
 }
 
 impl PartialEq for Match {
     fn eq(&self, other: &Self) -> bool {
-        if self.content == other.content
+        self.content == other.content
             && self.start_pos == other.start_pos
             && self.end_pos == other.end_pos
-        {
-            return true;
+            && self.pattern == other.pattern
+            && self.groups == other.groups
+            && self.location == other.location
+    }
+}
+
+/// Location of a match spanning lines
+///
+/// Lines and columns start at 1, columns are counted in characters and end column is the
+/// column after the last character of the match.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Location {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Location {
+    pub fn new(start_line: usize, start_column: usize, end_line: usize, end_column: usize) -> Self {
+        Self {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
         }
-        return false;
+    }
+}
+
+fn is_first_pattern(pattern: &usize) -> bool {
+    *pattern == 0
+}
+
+/// Capture group of a match, positions are relative to the line
+#[derive(Debug, Serialize)]
+pub struct Group {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub content: String,
+    pub start_pos: usize,
+    pub end_pos: usize,
+}
+
+impl Group {
+    pub fn new(
+        index: usize,
+        name: Option<String>,
+        content: String,
+        start_pos: usize,
+        end_pos: usize,
+    ) -> Self {
+        Self {
+            index,
+            name,
+            content,
+            start_pos,
+            end_pos,
+        }
+    }
+}
+
+impl PartialEq for Group {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+            && self.name == other.name
+            && self.content == other.content
+            && self.start_pos == other.start_pos
+            && self.end_pos == other.end_pos
     }
 }
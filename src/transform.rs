@@ -0,0 +1,202 @@
+use std::io::{BufRead, Error, ErrorKind, Result};
+use std::str::FromStr;
+
+use regex::{Captures, RegexBuilder};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Text operation
+///
+/// Parsed from names like `upper`, `trim` or `pad:20`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operation {
+    Upper,
+    Lower,
+    Title,
+    Trim,
+    Slugify,
+    Nfc,
+    Nfkc,
+    Reverse,
+    // Pad end to the width
+    Pad(usize),
+    // Pad start to the width
+    PadLeft(usize),
+}
+
+impl Operation {
+    pub fn apply(&self, content: &str) -> String {
+        match self {
+            Operation::Upper => upper(content),
+            Operation::Lower => lower(content),
+            Operation::Title => title(content),
+            Operation::Trim => trim(content),
+            Operation::Slugify => slugify(content),
+            Operation::Nfc => nfc(content),
+            Operation::Nfkc => nfkc(content),
+            Operation::Reverse => reverse(content),
+            Operation::Pad(width) => pad(content, *width),
+            Operation::PadLeft(width) => pad_left(content, *width),
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        let (name, width) = match name.split_once(':') {
+            Some((name, width)) => (name, Some(width)),
+            None => (name, None),
+        };
+
+        let operation = match (name, width) {
+            ("upper", None) => Operation::Upper,
+            ("lower", None) => Operation::Lower,
+            ("title", None) => Operation::Title,
+            ("trim", None) => Operation::Trim,
+            ("slugify", None) => Operation::Slugify,
+            ("nfc", None) => Operation::Nfc,
+            ("nfkc", None) => Operation::Nfkc,
+            ("reverse", None) => Operation::Reverse,
+            ("pad", Some(width)) => Operation::Pad(parse_width(width)?),
+            ("pad-left", Some(width)) => Operation::PadLeft(parse_width(width)?),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unknown operation {}", name),
+                ))
+            }
+        };
+        Ok(operation)
+    }
+}
+
+fn parse_width(width: &str) -> Result<usize> {
+    width
+        .parse()
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("Invalid width {}", width)))
+}
+
+/// Apply operations to content in order
+pub fn transform(content: &str, operations: &[Operation]) -> String {
+    operations
+        .iter()
+        .fold(content.to_string(), |content, operation| {
+            operation.apply(&content)
+        })
+}
+
+/// Transform lines
+///
+/// With pattern only matches are transformed, otherwise whole lines. Content is returned
+/// with original line endings.
+pub fn transform_lines<R: BufRead>(
+    reader: &mut R,
+    pattern: Option<&str>,
+    ignore_case: &bool,
+    operations: &[Operation],
+) -> Result<String> {
+    let target_pattern = pattern.map(|pattern| {
+        RegexBuilder::new(pattern)
+            .case_insensitive(*ignore_case)
+            .build()
+            .unwrap()
+    });
+
+    let mut results = String::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let line_ending = &line[content.len()..];
+        match &target_pattern {
+            Some(target_pattern) => {
+                results.push_str(&target_pattern.replace_all(content, |captures: &Captures| {
+                    transform(&captures[0], operations)
+                }))
+            }
+            None => results.push_str(&transform(content, operations)),
+        }
+        results.push_str(line_ending);
+        line.clear();
+    }
+
+    Ok(results)
+}
+
+pub fn upper(content: &str) -> String {
+    content.to_uppercase()
+}
+
+pub fn lower(content: &str) -> String {
+    content.to_lowercase()
+}
+
+/// Uppercase first letter of each word, lowercase the rest
+pub fn title(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut word_start = true;
+    for c in content.chars() {
+        if c.is_alphanumeric() {
+            if word_start {
+                result.extend(c.to_uppercase());
+            } else {
+                result.extend(c.to_lowercase());
+            }
+            word_start = false;
+        } else {
+            result.push(c);
+            word_start = !matches!(c, '\'' | '’');
+        }
+    }
+    result
+}
+
+pub fn trim(content: &str) -> String {
+    content.trim().to_string()
+}
+
+/// Convert to lowercase ascii words separated by dashes, ie `Crème Brûlée!` to `creme-brulee`
+pub fn slugify(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    for c in content.nfkd().filter(|c| !is_combining_mark(*c)) {
+        if c.is_ascii_alphanumeric() {
+            result.push(c.to_ascii_lowercase());
+        } else if !result.is_empty() && !result.ends_with('-') {
+            result.push('-');
+        }
+    }
+    result.trim_end_matches('-').to_string()
+}
+
+/// Unicode canonical composition
+pub fn nfc(content: &str) -> String {
+    content.nfc().collect()
+}
+
+/// Unicode compatibility composition
+pub fn nfkc(content: &str) -> String {
+    content.nfkc().collect()
+}
+
+/// Reverse characters, keeping combining marks with their base character
+pub fn reverse(content: &str) -> String {
+    let mut clusters: Vec<String> = vec![];
+    for c in content.chars() {
+        match clusters.last_mut() {
+            Some(cluster) if is_combining_mark(c) => cluster.push(c),
+            _ => clusters.push(c.to_string()),
+        }
+    }
+    clusters.into_iter().rev().collect()
+}
+
+/// Pad end with spaces to the width in characters
+pub fn pad(content: &str, width: usize) -> String {
+    format!("{:<width$}", content, width = width)
+}
+
+/// Pad start with spaces to the width in characters
+pub fn pad_left(content: &str, width: usize) -> String {
+    format!("{:>width$}", content, width = width)
+}
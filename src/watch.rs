@@ -0,0 +1,43 @@
+use std::fs::metadata;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Watcher of input files changes, polling their size and modification time
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    // Missing files have no state
+    states: Vec<Option<(u64, Option<SystemTime>)>>,
+}
+
+impl Watcher {
+    pub fn new(paths: &[PathBuf]) -> Self {
+        let mut watcher = Self {
+            paths: paths.to_vec(),
+            states: vec![],
+        };
+        watcher.states = watcher.get_states();
+        watcher
+    }
+
+    /// Check whether any file changed, was created or removed since last check
+    pub fn has_changed(&mut self) -> bool {
+        let states = self.get_states();
+        if states == self.states {
+            return false;
+        }
+
+        self.states = states;
+        true
+    }
+
+    fn get_states(&self) -> Vec<Option<(u64, Option<SystemTime>)>> {
+        self.paths
+            .iter()
+            .map(|path| {
+                metadata(path)
+                    .ok()
+                    .map(|file_metadata| (file_metadata.len(), file_metadata.modified().ok()))
+            })
+            .collect()
+    }
+}
@@ -1,39 +1,63 @@
 use std::fs::File;
-use std::io::{stdout, Result};
+use std::io::{stdout, IsTerminal, Result};
 use std::path::PathBuf;
 
 use okolib::output_destination::OutputDestination;
-use owo_colors::AnsiColors;
 
+use okolib::output_color::OutputColor;
 use okolib::output_format::OutputFormat;
 use okolib::output_style::OutputStyle;
 use okolib::output_writer::OutputWriter;
 
+use crate::config::Config;
+
 pub fn get_writer<'a>(
     output_path: Option<PathBuf>,
     output_format: OutputFormat,
+    output_color: OutputColor,
     line_number: bool,
-    content_color: Option<AnsiColors>,
-    match_color: Option<AnsiColors>,
+    cfg: &Config,
 ) -> Result<OutputWriter<'a>> {
-    if output_path.is_none() {
-        // Get style
-        let output_style = OutputStyle::new(line_number, content_color, match_color);
+    // Get style
+    let mut output_style = OutputStyle::new(
+        line_number,
+        cfg.get_content_color()?,
+        cfg.get_match_color()?,
+    );
+    output_style.line_number_color = cfg.get_line_number_color()?;
+    output_style.path_color = cfg.get_path_color()?;
+    output_style.separator_color = cfg.get_separator_color()?;
+    output_style.match_palette = cfg.get_match_palette()?;
+
+    match output_path {
+        None => {
+            let color = output_color.is_enabled(stdout().is_terminal());
+
+            let base_writer = Box::new(stdout());
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                output_format,
+                Some(output_style),
+            );
+            writer.set_color(color);
+            Ok(writer)
+        }
+        Some(file_path) => {
+            // Colors are written to files only if forced, style is kept for formats like html
+            let color = output_color.is_enabled(false);
+            // Line number prefixes are written only to console, as before
+            output_style.line_number = false;
 
-        let base_writer = Box::new(stdout());
-        Ok(OutputWriter::new(
-            base_writer,
-            OutputDestination::Standard,
-            output_format,
-            Some(output_style),
-        ))
-    } else {
-        let base_writer = Box::new(File::create(output_path.unwrap()).unwrap());
-        Ok(OutputWriter::new(
-            base_writer,
-            OutputDestination::File,
-            output_format,
-            None,
-        ))
+            let base_writer = Box::new(File::create(file_path).unwrap());
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                output_format,
+                Some(output_style),
+            );
+            writer.set_color(color);
+            Ok(writer)
+        }
     }
 }
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod allocations_tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::io::BufReader;
+
+    use okolib::search::{count_breakdown, search_patterns};
+
+    /// Allocator counting allocations of the current thread, so tests can run in parallel
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn count_allocations<F: FnOnce()>(f: F) -> usize {
+        let before = ALLOCATIONS.with(|allocations| allocations.get());
+        f();
+        ALLOCATIONS.with(|allocations| allocations.get()) - before
+    }
+
+    fn get_content(lines: usize) -> String {
+        (0..lines)
+            .map(|idx| format!("2024-01-01 INFO request id={} ok\n", idx))
+            .collect()
+    }
+
+    #[test]
+    fn test_count_breakdown_allocations_independent_of_lines() {
+        let patterns = vec![r"id=\d+".to_string(), "ok".to_string()];
+        let get_allocations = |content: &str| {
+            count_allocations(|| {
+                let mut reader = BufReader::new(content.as_bytes());
+                let results =
+                    count_breakdown(&mut reader, &patterns, &false, &false, &false).unwrap();
+                assert!(results.total > 0);
+            })
+        };
+
+        let small = get_allocations(&get_content(100));
+        let large = get_allocations(&get_content(10_000));
+
+        // Only line buffer grows, nothing is allocated per line
+        assert!(large < small + 50, "{} vs {}", large, small);
+    }
+
+    #[test]
+    fn test_search_patterns_allocates_only_matching_lines() {
+        let content = get_content(10_000);
+        let patterns = vec!["id=42 ".to_string()];
+
+        let allocations = count_allocations(|| {
+            let mut reader = BufReader::new(content.as_bytes());
+            let results = search_patterns(&mut reader, &patterns, &false, &false).unwrap();
+            assert_eq!(results.results.len(), 1);
+        });
+
+        assert!(allocations < 1_000, "{}", allocations);
+    }
+}
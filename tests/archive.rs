@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod archive_tests {
+    use std::io::{Cursor, Write};
+    use std::path::Path;
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use okolib::archive::Archive;
+    use okolib::compression::Compression;
+
+    const MEMBERS: [(&str, &str); 2] = [("logs/app.log", "one error\n"), ("conf/app.conf", "ok\n")];
+
+    fn tar_archive() -> Vec<u8> {
+        let mut builder = tar::Builder::new(vec![]);
+        for (path, content) in MEMBERS {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn zip_archive() -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(vec![]));
+        writer
+            .add_directory("logs/", SimpleFileOptions::default())
+            .unwrap();
+        for (path, content) in MEMBERS {
+            writer
+                .start_file(path, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn read_members(archive: &Archive, data: Vec<u8>) -> Vec<(String, String)> {
+        let mut members = vec![];
+        archive
+            .read_members(Cursor::new(data), |path, member| {
+                let mut content = String::new();
+                member.read_to_string(&mut content)?;
+                members.push((path.to_string(), content));
+                Ok(())
+            })
+            .unwrap();
+        members
+    }
+
+    fn expected_members() -> Vec<(String, String)> {
+        MEMBERS
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            Archive::from_path(Path::new("bundle.zip")),
+            Some(Archive::Zip)
+        );
+        assert_eq!(
+            Archive::from_path(Path::new("bundle.tar")),
+            Some(Archive::Tar(None))
+        );
+        assert_eq!(
+            Archive::from_path(Path::new("bundle.TAR.GZ")),
+            Some(Archive::Tar(Some(Compression::Gzip)))
+        );
+        assert_eq!(
+            Archive::from_path(Path::new("bundle.tzst")),
+            Some(Archive::Tar(Some(Compression::Zstd)))
+        );
+        assert_eq!(Archive::from_path(Path::new("app.log.gz")), None);
+    }
+
+    #[test]
+    fn test_read_tar_members() {
+        let members = read_members(&Archive::Tar(None), tar_archive());
+
+        assert_eq!(members, expected_members());
+    }
+
+    #[test]
+    fn test_read_compressed_tar_members() {
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(&tar_archive()).unwrap();
+        let data = encoder.finish().unwrap();
+
+        let members = read_members(&Archive::Tar(Some(Compression::Gzip)), data);
+
+        assert_eq!(members, expected_members());
+    }
+
+    #[test]
+    fn test_read_zip_members() {
+        let members = read_members(&Archive::Zip, zip_archive());
+
+        assert_eq!(members, expected_members());
+    }
+}
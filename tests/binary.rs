@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod binary_tests {
+    use std::io::{BufRead, BufReader, Read};
+
+    use okolib::binary::{is_binary, LossyReader, BLOCK_SIZE};
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"plain text\n"));
+        assert!(!is_binary("ünïcode".as_bytes()));
+        assert!(is_binary(b"\x7fELF\x02\x01\x01\x00"));
+
+        // Only first block is checked
+        let mut content = vec![b'a'; BLOCK_SIZE];
+        content.push(0);
+        assert!(!is_binary(&content));
+    }
+
+    #[test]
+    fn test_lossy_reader_lines() {
+        let data = b"caf\xe9\nok\r\n\xff\xfe end";
+        let reader = LossyReader::new(BufReader::new(&data[..]));
+
+        let lines: Vec<String> = reader.lines().map(|line| line.unwrap()).collect();
+
+        assert_eq!(lines, vec!["caf\u{FFFD}", "ok", "\u{FFFD}\u{FFFD} end"]);
+    }
+
+    #[test]
+    fn test_lossy_reader_keeps_valid_content() {
+        let data = "ünïcode\n\0binary\n".as_bytes();
+        let mut reader = LossyReader::new(BufReader::with_capacity(2, data));
+
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+
+        assert_eq!(content, "ünïcode\n\0binary\n");
+    }
+}
@@ -3,12 +3,19 @@ mod content;
 
 #[cfg(test)]
 mod cli_tests {
-    use std::fs::{read_to_string, remove_file};
-
+    use std::fs::{read_to_string, remove_file, OpenOptions};
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::Stdio;
+    use std::sync::mpsc::channel;
+    use std::thread::{sleep, spawn};
+    use std::time::Duration;
+
+    use assert_cmd::cargo::CommandCargoExt;
     use assert_cmd::Command;
     use assert_fs::prelude::*;
     use assert_fs::NamedTempFile;
     use predicates::ord::eq;
+    use predicates::prelude::PredicateBooleanExt;
     use predicates::str::contains;
 
     use super::content::content::CONTENT;
@@ -17,11 +24,10 @@ mod cli_tests {
     #[test]
     fn test_default_command_config_file_not_found() {
         confy::get_configuration_file_path("oko", "local")
-            .and_then(|file_path| {
+            .map(|file_path| {
                 if file_path.exists() {
                     remove_file(file_path).unwrap();
                 }
-                Ok(())
             })
             .unwrap();
 
@@ -32,6 +38,8 @@ mod cli_tests {
 
         cmd.arg("concert").arg("-i").arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
@@ -76,6 +84,32 @@ mod cli_tests {
         search_output_file.close().unwrap();
     }
 
+    #[test]
+    fn test_default_command_plain_output_to_file_without_line_number() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let search_output_file = NamedTempFile::new("output-line-number.txt").unwrap();
+        search_output_file.write_str("").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-n")
+            .arg("-o")
+            .arg(search_output_file.path());
+
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(search_output, "I'm here for a concert.\n");
+
+        file.close().unwrap();
+        search_output_file.close().unwrap();
+    }
+
     #[test]
     fn test_default_command_json_output_to_file() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
@@ -103,6 +137,97 @@ mod cli_tests {
         search_output_file.close().unwrap();
     }
 
+    #[test]
+    fn test_default_command_html_output_to_file() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let search_output_file = NamedTempFile::new("output.html").unwrap();
+        search_output_file.write_str("").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-o")
+            .arg(search_output_file.path())
+            .arg("-f")
+            .arg("html");
+
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert!(search_output.starts_with("<!DOCTYPE html>"));
+        assert!(search_output.contains("<tr><td class=\"line\">5</td><td>I&#39;m here for a <mark style=\"color:#00a000\">concert</mark>.</td></tr>"));
+
+        file.close().unwrap();
+        search_output_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_default_command_markdown_output_to_file() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let search_output_file = NamedTempFile::new("output.md").unwrap();
+        search_output_file.write_str("").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-o")
+            .arg(search_output_file.path())
+            .arg("-f")
+            .arg("markdown");
+
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(
+            search_output,
+            "## Results\n\n```text\n5: I'm here for a concert.\n```\n"
+        );
+
+        file.close().unwrap();
+        search_output_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_default_command_multiple_input_files() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let other_file = NamedTempFile::new("content-3.txt").unwrap();
+        other_file.write_str("Going to a concert tonight.").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-i")
+            .arg(other_file.path())
+            .arg("-f")
+            .arg("json");
+
+        cmd.assert()
+            .success()
+            .stdout(contains(format!(
+                "{{\"path\":\"{}\",\"line\":5,",
+                file.path().display()
+            )))
+            .stdout(contains(format!(
+                "{{\"path\":\"{}\",\"line\":1,",
+                other_file.path().display()
+            )));
+
+        file.close().unwrap();
+        other_file.close().unwrap();
+    }
+
     #[test]
     fn test_default_command_output_format_plain() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
@@ -112,6 +237,8 @@ mod cli_tests {
 
         cmd.arg("concert").arg("-i").arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
@@ -119,6 +246,117 @@ mod cli_tests {
         file.close().unwrap();
     }
 
+    #[test]
+    fn test_default_command_color_auto_not_terminal() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .env_remove("NO_COLOR")
+            .env_remove("CLICOLOR_FORCE");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("I'm here for a concert.\n"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_default_command_color_never() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--color")
+            .arg("never")
+            .env("CLICOLOR_FORCE", "1");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("I'm here for a concert.\n"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_default_command_color_clicolor_force() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .env_remove("NO_COLOR")
+            .env("CLICOLOR_FORCE", "1");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("I'm here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_default_command_color_no_color() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .env("NO_COLOR", "1")
+            .env("CLICOLOR_FORCE", "1");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("I'm here for a concert.\n"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_default_command_color_always_output_to_file() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let search_output_file = NamedTempFile::new("output.txt").unwrap();
+        search_output_file.write_str("").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-o")
+            .arg(search_output_file.path())
+            .arg("--color")
+            .arg("always");
+
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(
+            search_output,
+            "I'm here for a \u{1b}[32mconcert\u{1b}[39m.\n"
+        );
+
+        file.close().unwrap();
+        search_output_file.close().unwrap();
+    }
+
     #[test]
     fn test_default_command_output_format_json() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
@@ -148,6 +386,8 @@ mod cli_tests {
 
         cmd.arg("concert").arg("-i").arg(file.path()).arg("-n");
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert().success().stdout(contains(
             "5: I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n",
         ));
@@ -164,6 +404,8 @@ mod cli_tests {
 
         cmd.arg("here").arg("-c").arg("-i").arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert().success().stdout(contains(
             "Nice to meet you too, Jack. What brings you \u{1b}[32mhere\u{1b}[39m?\n\
             I\'m \u{1b}[32mhere\u{1b}[39m shopping. How about you? What brings you \u{1b}[32mhere\u{1b}[39m?\n\
@@ -183,6 +425,8 @@ mod cli_tests {
 
         cmd.arg(r"\bconcert\b").arg("-i").arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
@@ -199,6 +443,8 @@ mod cli_tests {
 
         cmd.arg(r"\bhere\b").arg("-c").arg("-i").arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert().success().stdout(contains(
             "Nice to meet you too, Jack. What brings you \u{1b}[32mhere\u{1b}[39m?\n\
             I\'m \u{1b}[32mhere\u{1b}[39m shopping. How about you? What brings you \u{1b}[32mhere\u{1b}[39m?\n\
@@ -209,6 +455,36 @@ mod cli_tests {
         file.close().unwrap();
     }
 
+    #[test]
+    fn test_default_command_multiple_patterns() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("concert")
+            .arg("-e")
+            .arg("Jane")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-f")
+            .arg("json");
+
+        cmd.assert()
+            .success()
+            .stdout(contains(
+                "\"pattern\":\"concert|Jane\",\"patterns\":[\"concert\",\"Jane\"]",
+            ))
+            .stdout(contains(
+                "{\"content\":\"Jane\",\"start_pos\":3,\"end_pos\":7,\"pattern\":1}",
+            ))
+            .stdout(contains(
+                "{\"content\":\"concert\",\"start_pos\":15,\"end_pos\":22}",
+            ));
+
+        file.close().unwrap();
+    }
+
     #[test]
     fn test_default_command_log_level_info() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
@@ -222,6 +498,8 @@ mod cli_tests {
             .arg("-l")
             .arg("info");
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"))
@@ -240,6 +518,8 @@ mod cli_tests {
         )
         .arg("concert");
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
@@ -316,6 +596,34 @@ mod cli_tests {
         search_output_file.close().unwrap();
     }
 
+    #[test]
+    fn test_search_command_toml_output_to_file() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let search_output_file = NamedTempFile::new("output.toml").unwrap();
+        search_output_file.write_str("").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("search")
+            .arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-o")
+            .arg(search_output_file.path())
+            .arg("-f")
+            .arg("toml");
+
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(search_output, "pattern = \"concert\"\n\n[[results]]\nline = 5\ncontent = \"I'm here for a concert.\"\n\n[[results.matches]]\ncontent = \"concert\"\nstart_pos = 15\nend_pos = 22\n");
+
+        file.close().unwrap();
+        search_output_file.close().unwrap();
+    }
+
     #[test]
     fn test_search_command_output_format_plain() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
@@ -325,6 +633,8 @@ mod cli_tests {
 
         cmd.arg("search").arg("concert").arg("-i").arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
@@ -366,6 +676,8 @@ mod cli_tests {
             .arg(file.path())
             .arg("-n");
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert().success().stdout(contains(
             "5: I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n",
         ));
@@ -386,6 +698,8 @@ mod cli_tests {
             .arg("-i")
             .arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert().success().stdout(contains(
             "Nice to meet you too, Jack. What brings you \u{1b}[32mhere\u{1b}[39m?\n\
             I\'m \u{1b}[32mhere\u{1b}[39m shopping. How about you? What brings you \u{1b}[32mhere\u{1b}[39m?\n\
@@ -408,6 +722,8 @@ mod cli_tests {
             .arg("-i")
             .arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert()
             .success()
             .stdout(contains("I\'m here for a \u{1b}[32mconcert\u{1b}[39m.\n"));
@@ -428,6 +744,8 @@ mod cli_tests {
             .arg("-i")
             .arg(file.path());
 
+        cmd.arg("--color").arg("always");
+
         cmd.assert().success().stdout(contains(
             "Nice to meet you too, Jack. What brings you \u{1b}[32mhere\u{1b}[39m?\n\
             I\'m \u{1b}[32mhere\u{1b}[39m shopping. How about you? What brings you \u{1b}[32mhere\u{1b}[39m?\n\
@@ -503,28 +821,48 @@ mod cli_tests {
         cmd.assert().success().stdout(eq(""));
 
         let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
-        assert_eq!(search_output, "{\"results\":\"1\"}");
+        assert_eq!(
+            search_output,
+            "{\"results\":\"1\",\"pattern\":\"concert\",\"total\":1,\"lines\":1}"
+        );
 
         file.close().unwrap();
         search_output_file.close().unwrap();
     }
 
     #[test]
-    fn test_count_command_output_format_plain() {
+    fn test_count_command_yaml_output_to_file() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
         file.write_str(CONTENT).unwrap();
 
+        let search_output_file = NamedTempFile::new("output.yaml").unwrap();
+        search_output_file.write_str("").unwrap();
+
         let mut cmd = Command::cargo_bin("oko").unwrap();
 
-        cmd.arg("count").arg("concert").arg("-i").arg(file.path());
+        cmd.arg("count")
+            .arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-o")
+            .arg(search_output_file.path())
+            .arg("-f")
+            .arg("yaml");
 
-        cmd.assert().success().stdout(contains("1"));
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(
+            search_output,
+            "results: '1'\npattern: concert\ntotal: 1\nlines: 1\n"
+        );
 
         file.close().unwrap();
+        search_output_file.close().unwrap();
     }
 
     #[test]
-    fn test_count_command_output_format_json() {
+    fn test_count_command_multiple_patterns() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
         file.write_str(CONTENT).unwrap();
 
@@ -532,28 +870,65 @@ mod cli_tests {
 
         cmd.arg("count")
             .arg("concert")
+            .arg("-e")
+            .arg("Jane")
             .arg("-i")
             .arg(file.path())
-            .arg("-f")
-            .arg("json");
+            .arg("--color")
+            .arg("never");
 
         cmd.assert()
             .success()
-            .stdout(contains("{\"results\":\"1\"}"));
+            .stdout(eq("concert: 1\nJane: 2\nTotal: 3 matches in 3 lines\n"));
 
         file.close().unwrap();
     }
 
     #[test]
-    fn test_count_command_output_line_number() {
+    fn test_count_command_output_format_plain() {
         let file = NamedTempFile::new("content-1.txt").unwrap();
         file.write_str(CONTENT).unwrap();
 
         let mut cmd = Command::cargo_bin("oko").unwrap();
 
-        cmd.arg("count")
-            .arg("concert")
-            .arg("-i")
+        cmd.arg("count").arg("concert").arg("-i").arg(file.path());
+
+        cmd.assert().success().stdout(contains("1"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_count_command_output_format_json() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("count")
+            .arg("concert")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-f")
+            .arg("json");
+
+        cmd.assert().success().stdout(contains(
+            "{\"results\":\"1\",\"pattern\":\"concert\",\"total\":1,\"lines\":1}",
+        ));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_count_command_output_line_number() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("count")
+            .arg("concert")
+            .arg("-i")
             .arg(file.path())
             .arg("-n");
 
@@ -614,4 +989,1238 @@ mod cli_tests {
 
         file.close().unwrap();
     }
+
+    // Replace command
+
+    #[test]
+    fn test_replace_command_content_file_not_found() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("concert")
+            .arg("show")
+            .arg("-i")
+            .arg("content-2.txt");
+
+        cmd.assert()
+            .failure()
+            .code(101)
+            .stderr(contains("Error reading file content-2.txt".to_string()));
+    }
+
+    #[test]
+    fn test_replace_command_output_format_plain() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg(r"(?<name>Ja\w+)")
+            .arg("Mr. ${name}")
+            .arg("-i")
+            .arg(file.path());
+
+        cmd.assert().success().stdout(eq(CONTENT
+            .replace("Jane", "Mr. Jane")
+            .replace("Jack", "Mr. Jack")));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_plain_output_to_file() {
+        let search_output_file = NamedTempFile::new("output.txt").unwrap();
+        search_output_file.write_str("").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("I'm here for a concert.\nBye.\n")
+            .arg("replace")
+            .arg("(here) for a (concert)")
+            .arg("$2 is $1")
+            .arg("-o")
+            .arg(search_output_file.path());
+
+        cmd.assert().success().stdout(eq(""));
+
+        let search_output = read_to_string(search_output_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(search_output, "I'm concert is here.\nBye.\n");
+
+        search_output_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_in_place() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+        let backup_path = file.path().with_file_name("content-1.txt.bak");
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg(r"\bhere\b")
+            .arg("there")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--in-place")
+            .arg("--backup")
+            .arg(".bak");
+
+        cmd.assert().success().stdout(eq(""));
+
+        let content = read_to_string(file.path()).unwrap();
+        assert_eq!(content, CONTENT.replace(" here", " there"));
+        let backup = read_to_string(&backup_path).unwrap();
+        assert_eq!(backup, CONTENT);
+
+        remove_file(backup_path).unwrap();
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_dry_run() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("concert")
+            .arg("show")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--dry-run");
+
+        cmd.assert().success().stdout(contains(
+            "-I'm here for a concert.\n+I'm here for a show.\n",
+        ));
+
+        let content = read_to_string(file.path()).unwrap();
+        assert_eq!(content, CONTENT);
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_in_place_keeps_invalid_utf8() {
+        let file = NamedTempFile::new("content-latin1.txt").unwrap();
+        file.write_binary(b"caf\xe9 au lait\nI'm here.\n").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("here")
+            .arg("there")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--in-place");
+
+        cmd.assert().success();
+
+        assert_eq!(
+            std::fs::read(file.path()).unwrap(),
+            b"caf\xe9 au lait\nI'm there.\n"
+        );
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_in_place_keeps_utf8_bom() {
+        let file = NamedTempFile::new("content-bom.txt").unwrap();
+        file.write_binary(b"\xef\xbb\xbfhere\nhere\n").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("^here")
+            .arg("there")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--in-place");
+
+        cmd.assert().success();
+
+        assert_eq!(
+            std::fs::read(file.path()).unwrap(),
+            b"\xef\xbb\xbfthere\nthere\n"
+        );
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_in_place_keeps_utf16le() {
+        let utf16le = |content: &str| -> Vec<u8> {
+            content
+                .encode_utf16()
+                .flat_map(|c| c.to_le_bytes())
+                .collect()
+        };
+        let file = NamedTempFile::new("content-utf16.txt").unwrap();
+        file.write_binary(&utf16le("\u{FEFF}café here\r\n"))
+            .unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("here")
+            .arg("there")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--in-place");
+
+        cmd.assert().success();
+
+        assert_eq!(
+            std::fs::read(file.path()).unwrap(),
+            utf16le("\u{FEFF}café there\r\n")
+        );
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_replace_command_in_place_without_input() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("concert")
+            .arg("show")
+            .arg("--in-place");
+
+        cmd.assert().failure().code(2);
+    }
+
+    #[test]
+    fn test_replace_command_in_place_conflicts_with_output() {
+        let file = NamedTempFile::new("content-in-place-output.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        for (arg, value) in [("-o", "output.txt"), ("-f", "json")] {
+            let mut cmd = Command::cargo_bin("oko").unwrap();
+
+            cmd.arg("replace")
+                .arg("concert")
+                .arg("show")
+                .arg("-i")
+                .arg(file.path())
+                .arg("--in-place")
+                .arg(arg)
+                .arg(value);
+
+            cmd.assert().failure().code(2);
+        }
+
+        assert_eq!(read_to_string(file.path()).unwrap(), CONTENT);
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_command_only_matching() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("search")
+            .arg(r"you \w+")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--only-matching");
+
+        cmd.assert().success().stdout(eq(
+            "you too\nyou here\nyou here\nyou have\nyou have\nyou around\n",
+        ));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_extract_named_group() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("user=alice\nid=42\n")
+            .arg(r"(?<key>\w+)=(?<value>\w+)")
+            .arg("--extract")
+            .arg("value")
+            .arg("-n");
+
+        cmd.assert().success().stdout(eq("1: alice\n2: 42\n"));
+    }
+
+    #[test]
+    fn test_search_extract_unknown_group() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("user=alice\nid=42\n")
+            .arg(r"(?<key>\w+)=(?<value>\w+)")
+            .arg("--extract")
+            .arg("name");
+
+        cmd.assert()
+            .failure()
+            .code(2)
+            .stderr(contains("Unknown capture group name"));
+    }
+
+    // Transform command
+
+    #[test]
+    fn test_transform_command_matches() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("transform")
+            .arg(r"Ja\w+")
+            .arg("--op")
+            .arg("upper")
+            .arg("-i")
+            .arg(file.path());
+
+        cmd.assert()
+            .success()
+            .stdout(eq(CONTENT.replace("Jane", "JANE").replace("Jack", "JACK")));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_transform_command_whole_lines() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("  Hello World  \n")
+            .arg("transform")
+            .arg("--op")
+            .arg("trim")
+            .arg("--op")
+            .arg("reverse");
+
+        cmd.assert().success().stdout(eq("dlroW olleH\n"));
+    }
+
+    #[test]
+    fn test_transform_command_unknown_operation() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("Hello\n")
+            .arg("transform")
+            .arg("--op")
+            .arg("shout");
+
+        cmd.assert()
+            .failure()
+            .code(2)
+            .stderr(contains("Unknown operation shout"));
+    }
+
+    // Redact command
+
+    #[test]
+    fn test_redact_command_stdin() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("jane@example.com logged in from 10.0.0.1\nBye.\n")
+            .arg("redact");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("[REDACTED] logged in from [REDACTED]\nBye.\n"))
+            .stderr(eq("Redacted email: 1\nRedacted ipv4: 1\n"));
+    }
+
+    #[test]
+    fn test_redact_command_output_format_json() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("redact")
+            .arg("-e")
+            .arg(r"Ja\w+")
+            .arg("--no-detectors")
+            .arg("--mask-text")
+            .arg("***")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-f")
+            .arg("json");
+
+        cmd.assert()
+            .success()
+            .stdout(contains(
+                "Hi! I'm ***. What's your name?\\nHi ***, I'm ***. Nice to meet you.",
+            ))
+            .stdout(contains("\"counts\":{\"custom\":4}"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_redact_command_output_format_csv() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("jane@example.com, 10.0.0.1\nBye.\n")
+            .arg("redact")
+            .arg("-f")
+            .arg("csv");
+
+        cmd.assert().success().stdout(eq(
+            "kind,name,value\nline,1,\"[REDACTED], [REDACTED]\"\nline,2,Bye.\ncategory,email,1\ncategory,ipv4,1\n",
+        ));
+    }
+
+    // Named patterns
+
+    #[test]
+    fn test_search_named_pattern() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("Mail jane@example.com\nCall 555-0100\nid 10.0.0.1\n")
+            .arg("@email")
+            .arg("-e")
+            .arg("@ipv4")
+            .arg("--only-matching");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("jane@example.com\n10.0.0.1\n"));
+    }
+
+    #[test]
+    fn test_search_unknown_named_pattern() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("Hi\nping @admin\n").arg("@admin");
+
+        cmd.assert().success().stdout(eq("ping @admin\n"));
+    }
+
+    #[test]
+    fn test_search_escaped_named_pattern() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("jane@example.com\nsee @email\n")
+            .arg(r"\@email");
+
+        cmd.assert().success().stdout(eq("see @email\n"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let args: [&[&str]; 7] = [
+            &["("],
+            &["search", "a", "-e", "("],
+            &["count", "("],
+            &["replace", "(", "x"],
+            &["transform", "(", "--op", "upper"],
+            &["redact", "-e", "("],
+            &["a", "--record-start", "("],
+        ];
+        for args in args {
+            let mut cmd = Command::cargo_bin("oko").unwrap();
+
+            cmd.write_stdin("a(\n").args(args);
+
+            cmd.assert()
+                .failure()
+                .code(2)
+                .stderr(contains("regex parse error"))
+                .stderr(contains("panicked").not());
+        }
+    }
+
+    #[test]
+    fn test_patterns_list_command() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("patterns").arg("list");
+
+        cmd.assert()
+            .success()
+            .stdout(contains("@email"))
+            .stdout(contains("@aws-key"))
+            .stdout(contains("@jwt"));
+    }
+
+    #[test]
+    fn test_count_command_frequency() {
+        let file = NamedTempFile::new("content-1.txt").unwrap();
+        file.write_str(CONTENT).unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("count")
+            .arg(r"you (?<word>\w+)")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--frequency")
+            .arg("--group")
+            .arg("word")
+            .arg("--top")
+            .arg("3");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("2 have\n2 here\n1 around\n"));
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_count_command_frequency_csv() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("E42 E7\nE42\n")
+            .arg("count")
+            .arg(r"E\d+")
+            .arg("--frequency")
+            .arg("-f")
+            .arg("csv");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("value,count\nE42,2\nE7,1\n"));
+    }
+
+    #[test]
+    fn test_count_command_top_without_frequency() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("E42\n")
+            .arg("count")
+            .arg(r"E\d+")
+            .arg("--top")
+            .arg("3");
+
+        cmd.assert().failure().code(2);
+    }
+
+    #[test]
+    fn test_count_command_breakdown_per_file() {
+        let first_file = NamedTempFile::new("content-1.txt").unwrap();
+        first_file.write_str(CONTENT).unwrap();
+        let second_file = NamedTempFile::new("content-2.txt").unwrap();
+        second_file.write_str("here and here\n").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("count")
+            .arg(r"\bhere\b")
+            .arg("-i")
+            .arg(first_file.path())
+            .arg("-i")
+            .arg(second_file.path())
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert()
+            .success()
+            .stdout(contains("content-1.txt: 4 matches in 3 lines\n"))
+            .stdout(contains("content-2.txt: 2 matches in 1 line\n"))
+            .stdout(contains("Total: 6 matches in 4 lines\n"));
+
+        first_file.close().unwrap();
+        second_file.close().unwrap();
+    }
+
+    #[test]
+    fn test_count_command_breakdown_per_line() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("here and here\nnothing\nhere\n")
+            .arg("count")
+            .arg("here")
+            .arg("-n")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("1: 2\n3: 1\nTotal: 3 matches in 2 lines\n"));
+    }
+
+    // Overlapping matches
+
+    #[test]
+    fn test_count_command_overlapping() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("aaaa\n")
+            .arg("count")
+            .arg("aa")
+            .arg("--overlapping");
+
+        cmd.assert().success().stdout(eq("3"));
+    }
+
+    #[test]
+    fn test_search_command_overlapping_only_matching() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("ACGCGCA\n")
+            .arg("search")
+            .arg("CGC")
+            .arg("--overlapping")
+            .arg("--only-matching")
+            .arg("-f")
+            .arg("csv");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("path,line,match\n,1,CGC\n,1,CGC\n"));
+    }
+
+    // Multiline search
+
+    #[test]
+    fn test_search_command_multiline() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("SELECT *\nFROM users\nWHERE id = 1;\nSELECT 1;\n")
+            .arg("search")
+            .arg(r"SELECT.*?;")
+            .arg("--multiline")
+            .arg("-n")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert().success().stdout(eq(
+            "1: SELECT *\n2: FROM users\n3: WHERE id = 1;\n4: SELECT 1;\n",
+        ));
+    }
+
+    #[test]
+    fn test_search_command_multiline_json() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("one\ntwo\n")
+            .arg("e.t")
+            .arg("--multiline")
+            .arg("-f")
+            .arg("json");
+
+        cmd.assert().success().stdout(eq(
+            "{\"pattern\":\"e.t\",\"results\":[{\"line\":1,\"content\":\"one\\ntwo\",\"matches\":[{\"content\":\"e\\nt\",\"start_pos\":2,\"end_pos\":5,\"location\":{\"start_line\":1,\"start_column\":3,\"end_line\":2,\"end_column\":2}}]}]}",
+        ));
+    }
+
+    // Record search
+
+    #[test]
+    fn test_search_command_record_start() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin(
+            "2024-01-01 INFO start\n2024-01-01 ERROR boom\n  at foo\n2024-01-02 INFO ok\n",
+        )
+        .arg("search")
+        .arg("ERROR")
+        .arg("--record-start")
+        .arg(r"^\d{4}-")
+        .arg("-n")
+        .arg("--color")
+        .arg("never");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("2: 2024-01-01 ERROR boom\n3:   at foo\n"));
+    }
+
+    #[test]
+    fn test_search_command_record_separator() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("one\0two\nthree\0four")
+            .arg("t")
+            .arg("--record-separator")
+            .arg("nul")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert().success().stdout(eq("two\nthree\n"));
+    }
+
+    #[test]
+    fn test_count_command_record_separator() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("a\n--\nb a\n--\nb\n")
+            .arg("count")
+            .arg("a")
+            .arg("--record-separator")
+            .arg("\\n--\\n")
+            .arg("-n")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("1: 1\n3: 1\nTotal: 2 matches in 2 records\n"));
+    }
+
+    #[test]
+    fn test_search_command_record_start_conflicts_with_multiline() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("a\n")
+            .arg("a")
+            .arg("--record-start")
+            .arg("a")
+            .arg("--multiline");
+
+        cmd.assert().failure().code(2);
+    }
+
+    // Binary files
+
+    #[test]
+    fn test_search_command_binary_report() {
+        let binary_file = NamedTempFile::new("content.bin").unwrap();
+        binary_file.write_binary(b"here\x00there\nhere\n").unwrap();
+        let text_file = NamedTempFile::new("content-latin1.txt").unwrap();
+        text_file.write_binary(b"caf\xe9 here\n").unwrap();
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("here")
+            .arg("-i")
+            .arg(binary_file.path())
+            .arg("-i")
+            .arg(text_file.path())
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert()
+            .success()
+            .stdout(eq(format!(
+                "{}: caf\u{FFFD} here\n",
+                text_file.path().display()
+            )))
+            .stderr(eq(format!(
+                "{}: binary file matches\n",
+                binary_file.path().display()
+            )));
+    }
+
+    #[test]
+    fn test_search_command_binary_skip() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin(b"here\x00there\n".to_vec())
+            .arg("here")
+            .arg("--binary")
+            .arg("skip");
+
+        cmd.assert().success().stdout(eq("")).stderr(eq(""));
+    }
+
+    #[test]
+    fn test_search_command_binary_text() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin(b"here\x00there\n".to_vec())
+            .arg("search")
+            .arg("there")
+            .arg("--binary")
+            .arg("text")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert().success().stdout(eq("here\0there\n"));
+    }
+
+    #[test]
+    fn test_count_command_binary_report() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin(b"here\x00there\n".to_vec())
+            .arg("count")
+            .arg("here");
+
+        cmd.assert()
+            .success()
+            .stdout(eq("0"))
+            .stderr(eq("(standard input): binary file matches\n"));
+    }
+
+    // Encodings
+
+    #[test]
+    fn test_search_command_utf16_bom() {
+        let file = NamedTempFile::new("content-utf16.txt").unwrap();
+        let content: Vec<u8> = "\u{FEFF}café here\r\nthere\r\n"
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes())
+            .collect();
+        file.write_binary(&content).unwrap();
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("here")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-f")
+            .arg("json");
+
+        cmd.assert().success().stdout(eq(
+            "{\"pattern\":\"here\",\"results\":[{\"line\":1,\"content\":\"café here\",\"matches\":[{\"content\":\"here\",\"start_pos\":6,\"end_pos\":10}]},{\"line\":2,\"content\":\"there\",\"matches\":[{\"content\":\"here\",\"start_pos\":1,\"end_pos\":5}]}]}",
+        ));
+    }
+
+    #[test]
+    fn test_search_command_encoding() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin(b"caf\xe9 here\n".to_vec())
+            .arg("search")
+            .arg("é")
+            .arg("--encoding")
+            .arg("windows-1252")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert().success().stdout(eq("café here\n"));
+    }
+
+    #[test]
+    fn test_search_command_unknown_encoding() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin("here\n")
+            .arg("search")
+            .arg("here")
+            .arg("--encoding")
+            .arg("foo");
+
+        cmd.assert()
+            .failure()
+            .code(2)
+            .stderr(contains("Unknown encoding foo"))
+            .stderr(contains("panicked").not());
+    }
+
+    #[test]
+    fn test_replace_command_encoding_conflicts_with_in_place() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("a")
+            .arg("b")
+            .arg("-i")
+            .arg("content.txt")
+            .arg("--in-place")
+            .arg("--encoding")
+            .arg("latin1");
+
+        cmd.assert().failure().code(2);
+    }
+
+    #[test]
+    fn test_replace_command_encoding_conflicts_with_dry_run() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("a")
+            .arg("b")
+            .arg("-i")
+            .arg("content.txt")
+            .arg("--dry-run")
+            .arg("--encoding")
+            .arg("latin1");
+
+        cmd.assert().failure().code(2);
+    }
+
+    // Compressed inputs
+
+    fn gzip(content: &str) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_search_command_compressed_file() {
+        let file = NamedTempFile::new("content.log.gz").unwrap();
+        file.write_binary(&gzip("one here\ntwo\n")).unwrap();
+        let plain_file = NamedTempFile::new("content.log").unwrap();
+        plain_file.write_str("three here\n").unwrap();
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("here")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-i")
+            .arg(plain_file.path())
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert().success().stdout(eq(format!(
+            "{}: one here\n{}: three here\n",
+            file.path().display(),
+            plain_file.path().display()
+        )));
+    }
+
+    #[test]
+    fn test_count_command_decompress_stdin() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.write_stdin(zstd::encode_all("here\nthere\n".as_bytes(), 0).unwrap())
+            .arg("count")
+            .arg("here")
+            .arg("--decompress");
+
+        cmd.assert().success().stdout(eq("2"));
+    }
+
+    #[test]
+    fn test_replace_command_in_place_skips_compressed_file() {
+        let file = NamedTempFile::new("content.txt.gz").unwrap();
+        let content = gzip("here\n");
+        file.write_binary(&content).unwrap();
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("replace")
+            .arg("here")
+            .arg("there")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--in-place");
+
+        cmd.assert().success();
+        assert_eq!(std::fs::read(file.path()).unwrap(), content);
+    }
+
+    // Archives
+
+    fn zip_archive(members: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(vec![]));
+        for (path, content) in members {
+            writer
+                .start_file(*path, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_search_command_zip_members() {
+        let file = NamedTempFile::new("bundle.zip").unwrap();
+        file.write_binary(&zip_archive(&[
+            ("logs/app.log", "one here\n"),
+            ("logs/old/app.log", "two here\n"),
+            ("conf/app.conf", "three here\n"),
+        ]))
+        .unwrap();
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("here")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--glob")
+            .arg("logs/*.log")
+            .arg("--color")
+            .arg("never");
+
+        cmd.assert().success().stdout(eq(format!(
+            "{}!/logs/app.log: one here\n",
+            file.path().display()
+        )));
+    }
+
+    #[test]
+    fn test_count_command_tar_gz_members() {
+        let mut builder = tar::Builder::new(vec![]);
+        for (path, content) in [("a.log", "here\n"), ("b/c.log", "here there\n")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        let file = NamedTempFile::new("bundle.tar.gz").unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        encoder.write_all(&builder.into_inner().unwrap()).unwrap();
+        file.write_binary(&encoder.finish().unwrap()).unwrap();
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+
+        cmd.arg("count")
+            .arg("here")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--glob")
+            .arg("**/*.log")
+            .arg("--color")
+            .arg("never");
+
+        let path = file.path().display();
+        cmd.assert().success().stdout(eq(format!(
+            "{}!/a.log: 1 match in 1 line\n{}!/b/c.log: 2 matches in 1 line\nTotal: 3 matches in 2 lines\n",
+            path, path
+        )));
+    }
+
+    // Memory-mapped files
+
+    #[test]
+    fn test_search_command_mmap() {
+        let file = NamedTempFile::new("large.log").unwrap();
+        let mut content = String::new();
+        // Larger than minimal size of memory-mapped files
+        for idx in 0..60_000 {
+            if idx % 10_000 == 0 {
+                content.push_str(&format!("ERROR at {}\r\n", idx));
+            } else {
+                content.push_str(&format!("INFO request id={}\n", idx));
+            }
+        }
+        file.write_str(&content).unwrap();
+
+        let search = |pattern: &str| {
+            let mut outputs = vec![];
+            for no_mmap in [false, true] {
+                let mut cmd = Command::cargo_bin("oko").unwrap();
+                cmd.arg(pattern)
+                    .arg("-i")
+                    .arg(file.path())
+                    .arg("-n")
+                    .arg("--color")
+                    .arg("never");
+                if no_mmap {
+                    cmd.arg("--no-mmap");
+                }
+                outputs.push(cmd.assert().success().get_output().stdout.clone());
+            }
+            assert_eq!(outputs[0], outputs[1], "{}", pattern);
+            String::from_utf8(outputs[0].clone()).unwrap()
+        };
+
+        assert_eq!(
+            search("^ERROR at \\d+$"),
+            "1: ERROR at 0\n10001: ERROR at 10000\n20001: ERROR at 20000\n30001: ERROR at 30000\n40001: ERROR at 40000\n50001: ERROR at 50000\n"
+        );
+
+        // Anchors match at line boundaries, not only at boundaries of whole file
+        assert_eq!(
+            search("\\AERROR at [1-2]\\d+\\z"),
+            "10001: ERROR at 10000\n20001: ERROR at 20000\n"
+        );
+        assert_eq!(
+            search("(?-m)^INFO request id=5999\\d$"),
+            "59991: INFO request id=59990\n59992: INFO request id=59991\n59993: INFO request id=59992\n59994: INFO request id=59993\n59995: INFO request id=59994\n59996: INFO request id=59995\n59997: INFO request id=59996\n59998: INFO request id=59997\n59999: INFO request id=59998\n60000: INFO request id=59999\n"
+        );
+    }
+
+    #[test]
+    fn test_default_command_follow() {
+        let file = NamedTempFile::new("follow.log").unwrap();
+        file.write_str("old ERROR\n").unwrap();
+
+        let mut child = std::process::Command::cargo_bin("oko")
+            .unwrap()
+            .arg("ERROR")
+            .arg("--follow")
+            .arg("--from-end")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-n")
+            .arg("--color")
+            .arg("never")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // Lines are streamed, so they are read in background
+        let stdout = child.stdout.take().unwrap();
+        let (sender, receiver) = channel();
+        spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        sleep(Duration::from_millis(500));
+        let mut log = OpenOptions::new().append(true).open(file.path()).unwrap();
+        log.write_all(b"INFO\nnew ERROR\n").unwrap();
+        let line = receiver.recv_timeout(Duration::from_secs(10));
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert_eq!(line.unwrap(), "3: new ERROR");
+    }
+
+    #[test]
+    fn test_default_command_follow_requires_input() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+        cmd.arg("ERROR").arg("--follow").assert().failure().code(2);
+    }
+
+    #[test]
+    fn test_default_command_follow_requires_plain_output() {
+        let file = NamedTempFile::new("follow-format.log").unwrap();
+        file.write_str("ERROR\n").unwrap();
+
+        for (command, output_format) in [(None, "json"), (Some("search"), "csv")] {
+            let mut cmd = Command::cargo_bin("oko").unwrap();
+            if let Some(command) = command {
+                cmd.arg(command);
+            }
+            cmd.arg("ERROR")
+                .arg("--follow")
+                .arg("-i")
+                .arg(file.path())
+                .arg("-f")
+                .arg(output_format)
+                .assert()
+                .failure()
+                .code(2)
+                .stderr(contains("--follow supports only plain output format"));
+        }
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_command_from_end_requires_follow() {
+        let file = NamedTempFile::new("from-end.log").unwrap();
+        file.write_str("ERROR\n").unwrap();
+
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+        cmd.arg("search")
+            .arg("ERROR")
+            .arg("--from-end")
+            .arg("-i")
+            .arg(file.path())
+            .assert()
+            .failure()
+            .code(2);
+    }
+
+    #[test]
+    fn test_watch_command_count() {
+        let file = NamedTempFile::new("watch.log").unwrap();
+        file.write_str("ERROR one\n").unwrap();
+
+        let mut child = std::process::Command::cargo_bin("oko")
+            .unwrap()
+            .arg("watch")
+            .arg("ERROR")
+            .arg("-i")
+            .arg(file.path())
+            .arg("--count")
+            .arg("--color")
+            .arg("never")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // Runs are read in background
+        let stdout = child.stdout.take().unwrap();
+        let (sender, receiver) = channel();
+        spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+        let read_until = |expected: &str| {
+            let mut lines = vec![];
+            while let Ok(line) = receiver.recv_timeout(Duration::from_secs(10)) {
+                let found = line == expected;
+                lines.push(line);
+                if found {
+                    break;
+                }
+            }
+            lines
+        };
+
+        let first_run = read_until("1");
+        let mut log = OpenOptions::new().append(true).open(file.path()).unwrap();
+        log.write_all(b"ERROR two\nERROR three\n").unwrap();
+        let second_run = read_until("Change: +2");
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert_eq!(
+            first_run[0],
+            "Watching ".to_string() + &file.path().display().to_string() + ", run 1"
+        );
+        assert_eq!(first_run.last().unwrap(), "1");
+        assert_eq!(second_run.last().unwrap(), "Change: +2");
+        assert!(second_run.contains(&"3".to_string()));
+    }
+
+    #[test]
+    fn test_watch_command_count_removed_input() {
+        let file = NamedTempFile::new("watch-1.log").unwrap();
+        file.write_str("ERROR one\n").unwrap();
+        let removed_file = NamedTempFile::new("watch-2.log").unwrap();
+        removed_file.write_str("ERROR two\nERROR three\n").unwrap();
+        let removed_path = removed_file.path().display().to_string();
+
+        let mut child = std::process::Command::cargo_bin("oko")
+            .unwrap()
+            .arg("watch")
+            .arg("ERROR")
+            .arg("-i")
+            .arg(file.path())
+            .arg("-i")
+            .arg(removed_file.path())
+            .arg("--count")
+            .arg("--color")
+            .arg("never")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // Runs are read in background
+        let stdout = child.stdout.take().unwrap();
+        let (sender, receiver) = channel();
+        spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+        let read_until = |expected: &str| {
+            let mut lines = vec![];
+            while let Ok(line) = receiver.recv_timeout(Duration::from_secs(10)) {
+                let found = line == expected;
+                lines.push(line);
+                if found {
+                    break;
+                }
+            }
+            lines
+        };
+
+        let first_run = read_until("Total: 3 matches in 3 lines");
+        removed_file.close().unwrap();
+        let second_run = read_until("Change: -2");
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert_eq!(first_run.last().unwrap(), "Total: 3 matches in 3 lines");
+        assert!(second_run.contains(&format!("{}: 1 match in 1 line", file.path().display())));
+        assert!(second_run.contains(&format!("{}: -2", removed_path)));
+        assert_eq!(second_run.last().unwrap(), "Change: -2");
+    }
+
+    #[test]
+    fn test_watch_command_requires_input() {
+        let mut cmd = Command::cargo_bin("oko").unwrap();
+        cmd.arg("watch").arg("ERROR").assert().failure().code(2);
+    }
 }
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod compression_tests {
+    use std::io::{Read, Write};
+    use std::path::Path;
+
+    use okolib::compression::Compression;
+
+    const CONTENT: &str = "one error\ntwo\n";
+
+    fn compress(compression: &Compression, content: &[u8]) -> Vec<u8> {
+        match compression {
+            Compression::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+                encoder.write_all(content).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Bzip2 => {
+                let mut encoder =
+                    bzip2::write::BzEncoder::new(vec![], bzip2::Compression::default());
+                encoder.write_all(content).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Xz => {
+                let mut encoder = liblzma::write::XzEncoder::new(vec![], 6);
+                encoder.write_all(content).unwrap();
+                encoder.finish().unwrap()
+            }
+            Compression::Zstd => zstd::encode_all(content, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_detect_and_decompress() {
+        for compression in [
+            Compression::Gzip,
+            Compression::Bzip2,
+            Compression::Xz,
+            Compression::Zstd,
+        ] {
+            let data = compress(&compression, CONTENT.as_bytes());
+            assert_eq!(Compression::detect(&data), Some(compression.clone()));
+
+            let mut content = String::new();
+            compression
+                .decompress(&data[..])
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            assert_eq!(content, CONTENT, "{:?}", compression);
+        }
+
+        assert_eq!(Compression::detect(CONTENT.as_bytes()), None);
+        assert_eq!(Compression::detect(b""), None);
+    }
+
+    #[test]
+    fn test_decompress_concatenated_streams() {
+        let mut data = compress(&Compression::Gzip, b"one\n");
+        data.extend(compress(&Compression::Gzip, b"two\n"));
+
+        let mut content = String::new();
+        Compression::Gzip
+            .decompress(&data[..])
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+
+        assert_eq!(content, "one\ntwo\n");
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            Compression::from_path(Path::new("app.log.1.gz")),
+            Some(Compression::Gzip)
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("app.log.bz2")),
+            Some(Compression::Bzip2)
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("app.log.xz")),
+            Some(Compression::Xz)
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("app.log.zst")),
+            Some(Compression::Zstd)
+        );
+        assert_eq!(Compression::from_path(Path::new("app.log")), None);
+        assert_eq!(Compression::from_path(Path::new("gz")), None);
+    }
+}
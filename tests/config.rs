@@ -6,8 +6,9 @@ mod utils;
 
 #[cfg(test)]
 mod config_tests {
-    use owo_colors::AnsiColors;
-    use std::fs::remove_file;
+    use okolib::output_style::TextStyle;
+    use owo_colors::{AnsiColors, DynColors, XtermColors};
+    use std::fs::{create_dir_all, remove_file, write};
 
     use super::bin_config::{get_config, Config};
     use super::utils::utils::TestContext;
@@ -16,8 +17,35 @@ mod config_tests {
     fn test_struct_default() {
         let config = Config::new("red".to_string(), "blue".to_string());
 
-        assert_eq!(config.get_content_color().unwrap(), Some(AnsiColors::Red));
-        assert_eq!(config.get_match_color().unwrap(), Some(AnsiColors::Blue));
+        assert_eq!(
+            config.get_content_color().unwrap(),
+            Some(TextStyle::from(AnsiColors::Red))
+        );
+        assert_eq!(
+            config.get_match_color().unwrap(),
+            Some(TextStyle::from(AnsiColors::Blue))
+        );
+    }
+
+    #[test]
+    fn test_struct_styles() {
+        let config = Config::new("".to_string(), "bold #ff8800 on 236".to_string());
+
+        let match_color = config.get_match_color().unwrap().unwrap();
+        assert_eq!(config.get_content_color().unwrap(), None);
+        assert_eq!(match_color.color, Some(DynColors::Rgb(255, 136, 0)));
+        assert_eq!(
+            match_color.background,
+            Some(DynColors::Xterm(XtermColors::from(236)))
+        );
+        assert!(match_color.bold);
+    }
+
+    #[test]
+    fn test_struct_unknown_color() {
+        let config = Config::new("reddish".to_string(), "blue".to_string());
+
+        assert!(config.get_content_color().is_err());
     }
 
     #[test]
@@ -26,11 +54,10 @@ mod config_tests {
         let _context = TestContext::new(
             || {
                 confy::get_configuration_file_path("oko", config_name)
-                    .and_then(|file_path| {
+                    .map(|file_path| {
                         if file_path.exists() {
                             remove_file(file_path).unwrap();
                         }
-                        Ok(())
                     })
                     .unwrap();
             },
@@ -40,7 +67,15 @@ mod config_tests {
         let config = get_config(config_name).unwrap();
 
         assert_eq!(config.get_content_color().unwrap(), None);
-        assert_eq!(config.get_match_color().unwrap(), Some(AnsiColors::Green));
+        assert_eq!(
+            config.get_match_color().unwrap(),
+            Some(TextStyle::from(AnsiColors::Green))
+        );
+        assert_eq!(config.get_line_number_color().unwrap(), None);
+        assert_eq!(config.get_path_color().unwrap(), None);
+        assert_eq!(config.get_separator_color().unwrap(), None);
+        assert_eq!(config.get_match_palette().unwrap().len(), 5);
+        assert!(config.get_patterns().is_empty());
     }
 
     #[test]
@@ -61,11 +96,49 @@ mod config_tests {
             },
             || {
                 confy::get_configuration_file_path("oko", config_name)
-                    .and_then(|file_path| {
+                    .map(|file_path| {
+                        if file_path.exists() {
+                            remove_file(file_path).unwrap();
+                        }
+                    })
+                    .unwrap();
+            },
+        );
+
+        let config = get_config(config_name).unwrap();
+
+        assert_eq!(
+            config.get_content_color().unwrap(),
+            Some(TextStyle::from(AnsiColors::Red))
+        );
+        assert_eq!(
+            config.get_match_color().unwrap(),
+            Some(TextStyle::from(AnsiColors::Blue))
+        );
+    }
+
+    #[test]
+    fn test_get_config_patterns() {
+        let config_name = "test_get_config_patterns";
+        let _context = TestContext::new(
+            || {
+                confy::get_configuration_file_path("oko", config_name)
+                    .map(|file_path| {
+                        create_dir_all(file_path.parent().unwrap()).unwrap();
+                        write(
+                            file_path,
+                            "match_color = \"blue\"\n\n[patterns]\nticket = \"[A-Z]+-\\\\d+\"\n",
+                        )
+                        .unwrap();
+                    })
+                    .unwrap();
+            },
+            || {
+                confy::get_configuration_file_path("oko", config_name)
+                    .map(|file_path| {
                         if file_path.exists() {
                             remove_file(file_path).unwrap();
                         }
-                        Ok(())
                     })
                     .unwrap();
             },
@@ -73,7 +146,50 @@ mod config_tests {
 
         let config = get_config(config_name).unwrap();
 
-        assert_eq!(config.get_content_color().unwrap(), Some(AnsiColors::Red));
-        assert_eq!(config.get_match_color().unwrap(), Some(AnsiColors::Blue));
+        assert_eq!(
+            config.get_patterns().get("ticket"),
+            Some(&r"[A-Z]+-\d+".to_string())
+        );
+        assert_eq!(config.get_match_palette().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_get_config_unknown_color() {
+        let config_name = "test_get_config_unknown_color";
+        let _context = TestContext::new(
+            || {
+                confy::get_configuration_file_path("oko", config_name)
+                    .map(|file_path| {
+                        create_dir_all(file_path.parent().unwrap()).unwrap();
+                        write(file_path, "match_palette = [\"yellow\", \"reddish\"]\n").unwrap();
+                    })
+                    .unwrap();
+            },
+            || {
+                confy::get_configuration_file_path("oko", config_name)
+                    .map(|file_path| {
+                        if file_path.exists() {
+                            remove_file(file_path).unwrap();
+                        }
+                    })
+                    .unwrap();
+            },
+        );
+
+        let error = get_config(config_name).err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid configuration test_get_config_unknown_color: Unknown color reddish"
+        );
+    }
+
+    #[test]
+    fn test_text_style_from_str() {
+        assert_eq!(
+            "blue".parse::<TextStyle>().unwrap(),
+            TextStyle::from(AnsiColors::Blue)
+        );
+        assert!("reddish".parse::<TextStyle>().is_err());
     }
 }
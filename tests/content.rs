@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 pub mod content {
     pub const CONTENT: &str = "Hi! I'm Jane. What's your name?
 Hi Jane, I'm Jack. Nice to meet you.
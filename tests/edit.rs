@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod edit_tests {
+    use std::fs::read_to_string;
+    use std::path::Path;
+
+    use assert_fs::prelude::*;
+    use assert_fs::NamedTempFile;
+
+    use okolib::edit::{get_diff, write_file};
+
+    #[test]
+    fn test_write_file() {
+        let file = NamedTempFile::new("edit-1.txt").unwrap();
+        file.write_str("I'm here.\n").unwrap();
+
+        write_file(file.path(), b"I'm there.\n", None).unwrap();
+
+        assert_eq!(read_to_string(file.path()).unwrap(), "I'm there.\n");
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_write_file_with_backup() {
+        let file = NamedTempFile::new("edit-2.txt").unwrap();
+        file.write_str("I'm here.\n").unwrap();
+        let backup_path = file.path().with_file_name("edit-2.txt.bak");
+
+        write_file(file.path(), b"I'm there.\n", Some(".bak")).unwrap();
+
+        assert_eq!(read_to_string(file.path()).unwrap(), "I'm there.\n");
+        assert_eq!(read_to_string(&backup_path).unwrap(), "I'm here.\n");
+
+        file.close().unwrap();
+    }
+
+    #[test]
+    fn test_get_diff() {
+        let expected_diff =
+            "--- content.txt\n+++ content.txt\n@@ -1,2 +1,2 @@\n Hi!\n-I'm here.\n+I'm there.\n";
+
+        let diff = get_diff(
+            Path::new("content.txt"),
+            "Hi!\nI'm here.\n",
+            "Hi!\nI'm there.\n",
+        );
+
+        assert_eq!(expected_diff, diff);
+    }
+
+    #[test]
+    fn test_get_diff_unchanged() {
+        let diff = get_diff(Path::new("content.txt"), "Hi!\n", "Hi!\n");
+
+        assert_eq!("", diff);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod encoding_tests {
+    use std::io::{BufReader, Read};
+
+    use encoding_rs::{UTF_16BE, UTF_16LE, WINDOWS_1252};
+
+    use okolib::encoding::{decode, get_encoding, FileEncoding};
+    use okolib::search::search;
+
+    fn utf16le(content: &str) -> Vec<u8> {
+        content
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_get_encoding() {
+        assert_eq!(get_encoding("utf-16le").unwrap(), UTF_16LE);
+        assert_eq!(get_encoding("UTF-16BE").unwrap(), UTF_16BE);
+        assert_eq!(get_encoding("latin1").unwrap(), WINDOWS_1252);
+        assert_eq!(
+            get_encoding("klingon").unwrap_err().to_string(),
+            "Unknown encoding klingon"
+        );
+    }
+
+    #[test]
+    fn test_decode_bom() {
+        let data = utf16le("\u{FEFF}café\r\n");
+
+        let mut content = String::new();
+        decode(&data[..], None)
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "café\r\n");
+
+        // BOM takes precedence
+        let mut content = String::new();
+        decode(&data[..], Some(WINDOWS_1252))
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "café\r\n");
+    }
+
+    #[test]
+    fn test_decode_encoding() {
+        let data = b"caf\xe9 \x93quoted\x94\n";
+
+        let mut content = String::new();
+        decode(&data[..], Some(WINDOWS_1252))
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "café “quoted”\n");
+
+        // Without encoding content is passed through
+        let mut content = vec![];
+        decode(&data[..], None).read_to_end(&mut content).unwrap();
+        assert_eq!(content, data);
+    }
+
+    #[test]
+    fn test_search_decoded_positions() {
+        let data = utf16le("\u{FEFF}naïve café\nthere\n");
+        let mut reader = BufReader::new(decode(&data[..], None));
+
+        let results = search(&mut reader, "café", &false).unwrap();
+
+        assert_eq!(results.results.len(), 1);
+        assert_eq!(results.results[0].content, "naïve café");
+        assert_eq!(results.results[0].matches[0].start_pos, 7);
+        assert_eq!(results.results[0].matches[0].end_pos, 12);
+    }
+
+    #[test]
+    fn test_file_encoding() {
+        let cases = [
+            (
+                b"caf\xe9\n".to_vec(),
+                FileEncoding::Utf8,
+                b"caf\xe9\n".to_vec(),
+            ),
+            (
+                b"\xef\xbb\xbfcaf\xc3\xa9\n".to_vec(),
+                FileEncoding::Utf8Bom,
+                "café\n".as_bytes().to_vec(),
+            ),
+            (
+                utf16le("\u{FEFF}café\n"),
+                FileEncoding::Utf16LeBom,
+                "café\n".as_bytes().to_vec(),
+            ),
+        ];
+
+        for (data, expected_encoding, expected_content) in cases {
+            let encoding = FileEncoding::detect(&data);
+            assert_eq!(encoding, expected_encoding);
+
+            let content = encoding.decode(&data).unwrap();
+            assert_eq!(*content, expected_content);
+            assert_eq!(encoding.encode(&content), data);
+        }
+
+        // Unpaired surrogate
+        let data = [0xff, 0xfe, 0x00, 0xd8];
+        assert!(FileEncoding::detect(&data).decode(&data).is_none());
+    }
+}
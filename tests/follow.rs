@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod follow_tests {
+    use std::fs::{rename, write, OpenOptions};
+    use std::io::Write;
+    use std::path::Path;
+
+    use assert_fs::TempDir;
+
+    use okolib::follow::{Chunk, Follower};
+
+    fn append(path: &Path, content: &str) {
+        let mut file = OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn chunk(line: usize, content: &str) -> Chunk {
+        Chunk {
+            line,
+            content: content.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_follow_appended_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        write(&path, "one\ntwo\n").unwrap();
+
+        let mut follower = Follower::new(&path, false).unwrap();
+        assert_eq!(follower.read().unwrap(), vec![chunk(1, "one\ntwo\n")]);
+        assert_eq!(follower.read().unwrap(), vec![]);
+
+        // Incomplete line is read once complete
+        append(&path, "three\nfo");
+        assert_eq!(follower.read().unwrap(), vec![chunk(3, "three\n")]);
+        append(&path, "ur\n");
+        assert_eq!(follower.read().unwrap(), vec![chunk(4, "four\n")]);
+    }
+
+    #[test]
+    fn test_follow_from_end() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        write(&path, "one\ntwo\nthr").unwrap();
+
+        let mut follower = Follower::new(&path, true).unwrap();
+        assert_eq!(follower.read().unwrap(), vec![]);
+
+        append(&path, "ee\n");
+        assert_eq!(follower.read().unwrap(), vec![chunk(3, "three\n")]);
+    }
+
+    #[test]
+    fn test_follow_truncated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        write(&path, "one\ntwo\n").unwrap();
+
+        let mut follower = Follower::new(&path, true).unwrap();
+        write(&path, "new\n").unwrap();
+
+        assert_eq!(follower.read().unwrap(), vec![chunk(1, "new\n")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_rotated() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        write(&path, "one\n").unwrap();
+
+        let mut follower = Follower::new(&path, true).unwrap();
+        append(&path, "two\nthree");
+        rename(&path, dir.path().join("app.log.1")).unwrap();
+        assert_eq!(follower.read().unwrap(), vec![chunk(2, "two\n")]);
+
+        write(&path, "new\n").unwrap();
+        assert_eq!(
+            follower.read().unwrap(),
+            vec![chunk(3, "three\n"), chunk(1, "new\n")]
+        );
+    }
+}
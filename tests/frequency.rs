@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod frequency_tests {
+    use std::io::BufReader;
+
+    use okolib::frequency::{frequency, Frequency, FrequencyResults};
+    use okolib::search::search;
+
+    #[test]
+    fn test_frequency() {
+        let expected_results = FrequencyResults {
+            pattern: r"you \w+".to_string(),
+            total: 6,
+            distinct: 4,
+            results: vec![
+                Frequency::new("you have".to_string(), 2),
+                Frequency::new("you here".to_string(), 2),
+                Frequency::new("you around".to_string(), 1),
+                Frequency::new("you too".to_string(), 1),
+            ],
+        };
+
+        let data = "you too, you here\nyou here\nyou have\nyou have, you around\n".as_bytes();
+        let mut reader = BufReader::new(data);
+        let results = search(&mut reader, r"you \w+", &false).unwrap();
+
+        assert_eq!(expected_results, frequency(&results, None));
+    }
+
+    #[test]
+    fn test_frequency_group_top() {
+        let expected_results = FrequencyResults {
+            pattern: r"(?<code>\d{3}) ".to_string(),
+            total: 4,
+            distinct: 3,
+            results: vec![Frequency::new("500".to_string(), 2)],
+        };
+
+        let data = "GET / 500 \nGET /a 404 \nGET /b 500 \nGET /c 200 \n".as_bytes();
+        let mut reader = BufReader::new(data);
+        let mut results = search(&mut reader, r"(?<code>\d{3}) ", &false).unwrap();
+        results.extract(Some("code"));
+
+        assert_eq!(expected_results, frequency(&results, Some(1)));
+    }
+}
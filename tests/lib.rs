@@ -1,5 +1,19 @@
+mod allocations;
+mod archive;
+mod binary;
+mod compression;
+mod edit;
+mod encoding;
+mod follow;
+mod frequency;
 mod output;
+mod output_style;
+mod patterns;
+mod records;
+mod redact;
 mod search;
+mod transform;
+mod watch;
 
 #[cfg(test)]
 pub mod lib_tests {}
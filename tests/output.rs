@@ -3,12 +3,16 @@ mod output_tests {
     use owo_colors::AnsiColors;
     use std::io::Result;
 
-    use okolib::output::{write_count_results, write_search_results};
+    use okolib::count_results::{CountResults, PatternCount};
+    use okolib::frequency::{Frequency, FrequencyResults};
+    use okolib::output::{
+        write_count_results, write_frequency_results, write_replace_results, write_search_results,
+    };
     use okolib::output_destination::OutputDestination;
     use okolib::output_format::OutputFormat;
-    use okolib::output_style::OutputStyle;
+    use okolib::output_style::{OutputStyle, TextStyle};
     use okolib::output_writer::OutputWriter;
-    use okolib::search_results::{Match, SearchResult, SearchResults};
+    use okolib::search_results::{Group, Location, Match, SearchResult, SearchResults};
 
     fn get_search_results() -> Result<SearchResults> {
         let mut results = SearchResults::new("here".to_string());
@@ -64,7 +68,11 @@ mod output_tests {
                 base_writer,
                 OutputDestination::Standard,
                 OutputFormat::Plain,
-                Some(OutputStyle::new(false, None, Some(AnsiColors::Green))),
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
             );
             write_search_results(results, &mut writer);
         }
@@ -72,6 +80,175 @@ mod output_tests {
         assert_eq!(expected_output.as_bytes(), destination);
     }
 
+    #[test]
+    fn test_write_search_results_plain_path_line_number_styles() {
+        let expected_output = "\u{1b}[35mfirst.txt\u{1b}[39m\u{1b}[36m: \u{1b}[39m\u{1b}[33m5\u{1b}[39m\u{1b}[36m: \u{1b}[39mI'm \u{1b}[32;1mhere\u{1b}[0m for a concert.\n";
+        let mut results = SearchResults::new("here".to_string());
+        let mut result = SearchResult::new(5, "I'm here for a concert.".to_string());
+        result.set_path("first.txt".to_string());
+        result.add_match(Match::new("here".to_string(), 4, 8));
+        results.add_result(result);
+
+        let mut style = OutputStyle::new(true, None, Some(TextStyle::parse("bold green").unwrap()));
+        style.line_number_color = Some(TextStyle::from(AnsiColors::Yellow));
+        style.path_color = Some(TextStyle::from(AnsiColors::Magenta));
+        style.separator_color = Some(TextStyle::from(AnsiColors::Cyan));
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(style),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    fn get_group_search_results() -> Result<SearchResults> {
+        let mut results = SearchResults::new("(user)=(\\w+)|id=\\d+".to_string());
+        results.patterns = vec!["(user)=(\\w+)".to_string(), "id=\\d+".to_string()];
+        let mut result = SearchResult::new(1, "user=alice id=42".to_string());
+        let mut m = Match::new("user=alice".to_string(), 0, 10);
+        m.add_group(Group::new(1, None, "user".to_string(), 0, 4));
+        m.add_group(Group::new(2, None, "alice".to_string(), 5, 10));
+        result.add_match(m);
+        m = Match::new("id=42".to_string(), 11, 16);
+        m.pattern = 1;
+        result.add_match(m);
+        results.add_result(result);
+
+        Ok(results)
+    }
+
+    #[test]
+    fn test_write_search_results_plain_palette() {
+        let expected_output = "\u{1b}[33;4muser\u{1b}[0m\u{1b}[32m=\u{1b}[39m\u{1b}[36;4malice\u{1b}[0m \u{1b}[31mid=42\u{1b}[39m\n";
+        let results = get_group_search_results().unwrap();
+
+        let mut style = OutputStyle::new(false, None, Some(TextStyle::from(AnsiColors::Green)));
+        style.match_palette = vec![
+            TextStyle::from(AnsiColors::Yellow),
+            TextStyle::from(AnsiColors::Cyan),
+            TextStyle::from(AnsiColors::Red),
+        ];
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(style),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_plain_groups_without_palette() {
+        let expected_output = "\u{1b}[32muser\u{1b}[39m\u{1b}[32m=\u{1b}[39m\u{1b}[32malice\u{1b}[39m \u{1b}[32mid=42\u{1b}[39m\n";
+        let results = get_group_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_json_groups() {
+        let expected_output = "{\"pattern\":\"(user)=(\\\\w+)|id=\\\\d+\",\"patterns\":[\"(user)=(\\\\w+)\",\"id=\\\\d+\"],\"results\":[{\"line\":1,\"content\":\"user=alice id=42\",\"matches\":[{\"content\":\"user=alice\",\"start_pos\":0,\"end_pos\":10,\"groups\":[{\"index\":1,\"content\":\"user\",\"start_pos\":0,\"end_pos\":4},{\"index\":2,\"content\":\"alice\",\"start_pos\":5,\"end_pos\":10}]},{\"content\":\"id=42\",\"start_pos\":11,\"end_pos\":16,\"pattern\":1}]}]}";
+        let results = get_group_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Json,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_plain_color_disabled() {
+        let expected_output = "Nice to meet you too, Jack. What brings you here?\nI'm here shopping. How about you? What brings you here?\nI'm here for a concert.\n";
+        let results = get_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            writer.set_color(false);
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_plain_color_file() {
+        let expected_output = "I'm \u{1b}[32mhere\u{1b}[39m for a concert.\n";
+        let mut results = SearchResults::new("here".to_string());
+        let mut result = SearchResult::new(5, "I'm here for a concert.".to_string());
+        result.add_match(Match::new("here".to_string(), 4, 8));
+        results.add_result(result);
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            writer.set_color(true);
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
     #[test]
     fn test_write_search_results_json() {
         let expected_output = "{\"pattern\":\"here\",\"results\":[{\"line\":3,\"content\":\"Nice to meet you too, Jack. What brings you here?\",\"matches\":[{\"content\":\"here\",\"start_pos\":44,\"end_pos\":48}]},{\"line\":4,\"content\":\"I\'m here shopping. How about you? What brings you here?\",\"matches\":[{\"content\":\"here\",\"start_pos\":4,\"end_pos\":8},{\"content\":\"here\",\"start_pos\":50,\"end_pos\":54}]},{\"line\":5,\"content\":\"I\'m here for a concert.\",\"matches\":[{\"content\":\"here\",\"start_pos\":4,\"end_pos\":8}]}]}";
@@ -92,10 +269,218 @@ mod output_tests {
         assert_eq!(expected_output.as_bytes(), destination);
     }
 
+    #[test]
+    fn test_write_search_results_yaml() {
+        let expected_output = "pattern: here\nresults:\n- line: 3\n  content: Nice to meet you too, Jack. What brings you here?\n  matches:\n  - content: here\n    start_pos: 44\n    end_pos: 48\n- line: 4\n  content: I'm here shopping. How about you? What brings you here?\n  matches:\n  - content: here\n    start_pos: 4\n    end_pos: 8\n  - content: here\n    start_pos: 50\n    end_pos: 54\n- line: 5\n  content: I'm here for a concert.\n  matches:\n  - content: here\n    start_pos: 4\n    end_pos: 8\n";
+        let results = get_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Yaml,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_toml() {
+        let expected_output = "pattern = \"here\"\n\n[[results]]\nline = 3\ncontent = \"Nice to meet you too, Jack. What brings you here?\"\n\n[[results.matches]]\ncontent = \"here\"\nstart_pos = 44\nend_pos = 48\n\n[[results]]\nline = 4\ncontent = \"I'm here shopping. How about you? What brings you here?\"\n\n[[results.matches]]\ncontent = \"here\"\nstart_pos = 4\nend_pos = 8\n\n[[results.matches]]\ncontent = \"here\"\nstart_pos = 50\nend_pos = 54\n\n[[results]]\nline = 5\ncontent = \"I'm here for a concert.\"\n\n[[results.matches]]\ncontent = \"here\"\nstart_pos = 4\nend_pos = 8\n";
+        let results = get_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Toml,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_html() {
+        let mut results = get_search_results().unwrap();
+        results.results[0].set_path("first.txt".to_string());
+        results.results[1].set_path("second.txt".to_string());
+        results.results[2].set_path("second.txt".to_string());
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Html,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        let output = String::from_utf8(destination).unwrap();
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<tr><td>first.txt</td><td>1</td><td>1</td></tr>"));
+        assert!(output.contains("<tr><td>second.txt</td><td>2</td><td>3</td></tr>"));
+        assert!(output.contains("<tr><th>Total</th><th>3</th><th>4</th></tr>"));
+        assert!(output.contains("<h2>second.txt</h2>"));
+        assert!(output.contains("<tr><td class=\"line\">5</td><td>I&#39;m <mark style=\"color:#00a000\">here</mark> for a concert.</td></tr>"));
+        assert!(output.contains("<input id=\"filter\""));
+        assert!(output.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_write_search_results_markdown() {
+        let expected_output = "## first.txt\n\n```text\n3: Nice to meet you too, Jack. What brings you here?\n```\n\n## second.txt\n\n```text\n4: I'm here shopping. How about you? What brings you here?\n5: I'm here for a concert.\n```\n";
+        let mut results = get_search_results().unwrap();
+        results.results[0].set_path("first.txt".to_string());
+        results.results[1].set_path("second.txt".to_string());
+        results.results[2].set_path("second.txt".to_string());
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Markdown,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_markdown_without_colors() {
+        let results = get_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Markdown,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            writer.set_color(true);
+            write_search_results(results, &mut writer);
+        }
+
+        let output = String::from_utf8(destination).unwrap();
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("3: Nice to meet you too, Jack. What brings you here?\n"));
+    }
+
+    #[test]
+    fn test_write_search_results_markdown_fence() {
+        let expected_output = "## Results\n\n````text\n1: Use ```<b>**code**</b>``` blocks\n````\n";
+        let mut results = SearchResults::new(r"\*\*code\*\*".to_string());
+        let mut result = SearchResult::new(1, "Use ```<b>**code**</b>``` blocks".to_string());
+        result.add_match(Match::new("**code**".to_string(), 10, 18));
+        results.add_result(result);
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Markdown,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_markdown_multiline() {
+        let expected_output = "## Results\n\n```text\n 9: Error: boom\n10:   at a.rs:1\n```\n";
+        let mut results = SearchResults::new(r"boom\r?\n\s+at".to_string());
+        let mut result = SearchResult::new(9, "Error: boom\r\n  at a.rs:1".to_string());
+        result.add_match(Match::new("boom\r\n  at".to_string(), 7, 17));
+        results.add_result(result);
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Markdown,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    fn get_count_results() -> CountResults {
+        let mut results = CountResults::new("here".to_string());
+        results.add_line(3, &[1], false);
+        results.add_line(4, &[2], false);
+        results.add_line(5, &[1], false);
+        results
+    }
+
+    fn get_count_results_breakdown() -> CountResults {
+        let mut results = CountResults::new("here|Jack".to_string());
+        results.patterns = vec![
+            PatternCount::new("here".to_string(), 0),
+            PatternCount::new("Jack".to_string(), 0),
+        ];
+
+        let mut first = CountResults::new("here|Jack".to_string());
+        first.patterns = vec![
+            PatternCount::new("here".to_string(), 0),
+            PatternCount::new("Jack".to_string(), 0),
+        ];
+        first.add_line(2, &[0, 1], true);
+        first.add_line(4, &[2, 0], true);
+        results.add_file("first.txt".to_string(), first);
+
+        let mut second = CountResults::new("here|Jack".to_string());
+        second.patterns = vec![
+            PatternCount::new("here".to_string(), 0),
+            PatternCount::new("Jack".to_string(), 0),
+        ];
+        second.add_line(1, &[1, 0], true);
+        results.add_file("second.txt".to_string(), second);
+
+        results
+    }
+
     #[test]
     fn test_write_count_results_plain() {
         let expected_output = "4";
-        let results = "4".to_string();
+        let results = get_count_results();
 
         let mut destination: Vec<u8> = Vec::new();
         {
@@ -114,8 +499,8 @@ mod output_tests {
 
     #[test]
     fn test_write_count_results_json() {
-        let expected_output = "{\"results\":\"4\"}";
-        let results = "4".to_string();
+        let expected_output = "{\"results\":\"4\",\"pattern\":\"here\",\"total\":4,\"lines\":3}";
+        let results = get_count_results();
 
         let mut destination: Vec<u8> = Vec::new();
         {
@@ -131,4 +516,404 @@ mod output_tests {
 
         assert_eq!(expected_output.as_bytes(), destination);
     }
+
+    #[test]
+    fn test_write_count_results_yaml() {
+        let expected_output = "results: '4'\npattern: here\ntotal: 4\nlines: 3\n";
+        let results = get_count_results();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Yaml,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_count_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_count_results_toml() {
+        let expected_output = "results = \"4\"\npattern = \"here\"\ntotal = 4\nlines = 3\n";
+        let results = get_count_results();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Toml,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_count_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_count_results_html() {
+        let results = get_count_results();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Html,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_count_results(results, &mut writer);
+        }
+
+        let output = String::from_utf8(destination).unwrap();
+        assert!(output.contains("<tr><th>Total</th><td>4</td></tr>"));
+    }
+
+    #[test]
+    fn test_write_count_results_markdown() {
+        let expected_output = "| Total |\n| ---: |\n| **4** |\n";
+        let results = get_count_results();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::File,
+                OutputFormat::Markdown,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_count_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_replace_results_plain() {
+        let expected_output = "I'm there for a concert.\n";
+        let results = "I'm there for a concert.\n".to_string();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_replace_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_replace_results_json() {
+        let expected_output = "{\"results\":\"I'm there for a concert.\\n\"}";
+        let results = "I'm there for a concert.\n".to_string();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Json,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_replace_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_replace_results_markdown() {
+        let expected_output = "```text\nI'm there for a concert.\n```\n";
+        let results = "I'm there for a concert.".to_string();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Markdown,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_replace_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_plain_extracted() {
+        let expected_output = "3: here\n4: here\n4: here\n5: here\n";
+        let mut results = get_search_results().unwrap();
+        results.extract(None);
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(true, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_search_results_json_extracted() {
+        let expected_output = "{\"pattern\":\"(?<key>[a-z]+)=([a-z]+)\",\"results\":[{\"line\":1,\"content\":\"user=alice\",\"matches\":[{\"content\":\"user=alice\",\"start_pos\":0,\"end_pos\":10,\"groups\":[{\"index\":1,\"name\":\"key\",\"content\":\"user\",\"start_pos\":0,\"end_pos\":4},{\"index\":2,\"content\":\"alice\",\"start_pos\":5,\"end_pos\":10}]}],\"extracted\":[\"alice\"]}]}";
+        let mut results = SearchResults::new("(?<key>[a-z]+)=([a-z]+)".to_string());
+        let mut result = SearchResult::new(1, "user=alice".to_string());
+        let mut m = Match::new("user=alice".to_string(), 0, 10);
+        m.add_group(Group::new(
+            1,
+            Some("key".to_string()),
+            "user".to_string(),
+            0,
+            4,
+        ));
+        m.add_group(Group::new(2, None, "alice".to_string(), 5, 10));
+        result.add_match(m);
+        results.add_result(result);
+        results.extract(Some("2"));
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Json,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    fn get_frequency_results() -> FrequencyResults {
+        FrequencyResults {
+            pattern: r"\d+".to_string(),
+            total: 13,
+            distinct: 2,
+            results: vec![
+                Frequency::new("404".to_string(), 12),
+                Frequency::new("5,0\"0".to_string(), 1),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_write_frequency_results_plain() {
+        let expected_output = "12 404\n 1 5,0\"0\n";
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_frequency_results(get_frequency_results(), &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_frequency_results_csv() {
+        let expected_output = "value,count\n404,12\n\"5,0\"\"0\",1\n";
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Csv,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_frequency_results(get_frequency_results(), &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_frequency_results_json() {
+        let expected_output = "{\"pattern\":\"\\\\d+\",\"total\":13,\"distinct\":2,\"results\":[{\"value\":\"404\",\"count\":12},{\"value\":\"5,0\\\"0\",\"count\":1}]}";
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Json,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_frequency_results(get_frequency_results(), &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    #[test]
+    fn test_write_search_results_csv() {
+        let expected_output = "path,line,match\n,3,here\n,4,here\n,4,here\n,5,here\n";
+        let results = get_search_results().unwrap();
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Csv,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(expected_output.as_bytes(), destination);
+    }
+
+    #[test]
+    fn test_write_count_results_plain_breakdown() {
+        let expected_output = "first.txt: 2: 1\nfirst.txt: 4: 2\nsecond.txt: 1: 1\nfirst.txt: 3 matches in 2 lines\nsecond.txt: 1 match in 1 line\nhere: 3\nJack: 1\nTotal: 4 matches in 3 lines\n";
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(true, None, None)),
+            );
+            write_count_results(get_count_results_breakdown(), &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    #[test]
+    fn test_write_count_results_json_breakdown() {
+        let expected_output = "{\"results\":\"4\",\"pattern\":\"here|Jack\",\"total\":4,\"lines\":3,\"patterns\":[{\"pattern\":\"here\",\"total\":3},{\"pattern\":\"Jack\",\"total\":1}],\"files\":[{\"path\":\"first.txt\",\"total\":3,\"lines\":2},{\"path\":\"second.txt\",\"total\":1,\"lines\":1}],\"line_counts\":[{\"path\":\"first.txt\",\"line\":2,\"total\":1},{\"path\":\"first.txt\",\"line\":4,\"total\":2},{\"path\":\"second.txt\",\"line\":1,\"total\":1}]}";
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Json,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_count_results(get_count_results_breakdown(), &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    #[test]
+    fn test_write_count_results_csv_breakdown() {
+        let expected_output = "kind,name,lines,total\nline,first.txt:2,1,1\nline,first.txt:4,1,2\nline,second.txt:1,1,1\nfile,first.txt,2,3\nfile,second.txt,1,1\npattern,here,,3\npattern,Jack,,1\ntotal,,3,4\n";
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Csv,
+                Some(OutputStyle::new(false, None, None)),
+            );
+            write_count_results(get_count_results_breakdown(), &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    #[test]
+    fn test_write_search_results_plain_overlapping() {
+        let expected_output =
+            "\u{1b}[32maa\u{1b}[39m\u{1b}[32ma\u{1b}[39m\u{1b}[32ma\u{1b}[39m b\n";
+        let mut results = SearchResults::new("aa".to_string());
+        let mut result = SearchResult::new(1, "aaaa b".to_string());
+        result.add_match(Match::new("aa".to_string(), 0, 2));
+        result.add_match(Match::new("aa".to_string(), 1, 3));
+        result.add_match(Match::new("aa".to_string(), 2, 4));
+        results.add_result(result);
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(
+                    false,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
+
+    #[test]
+    fn test_write_search_results_plain_multiline() {
+        let expected_output = "first.txt: 2: Error: \u{1b}[32mboom\u{1b}[39m\nfirst.txt: 3: \u{1b}[32m  at\u{1b}[39m a.rs:1\n";
+        let mut results = SearchResults::new(r"boom\r?\n\s+at".to_string());
+        let mut result = SearchResult::new(2, "Error: boom\r\n  at a.rs:1".to_string());
+        result.set_path("first.txt".to_string());
+        let mut m = Match::new("boom\r\n  at".to_string(), 7, 17);
+        m.location = Some(Location::new(2, 8, 3, 5));
+        result.add_match(m);
+        results.add_result(result);
+
+        let mut destination: Vec<u8> = Vec::new();
+        {
+            let base_writer = Box::new(&mut destination);
+            let mut writer = OutputWriter::new(
+                base_writer,
+                OutputDestination::Standard,
+                OutputFormat::Plain,
+                Some(OutputStyle::new(
+                    true,
+                    None,
+                    Some(TextStyle::from(AnsiColors::Green)),
+                )),
+            );
+            write_search_results(results, &mut writer);
+        }
+
+        assert_eq!(String::from_utf8(destination).unwrap(), expected_output);
+    }
 }
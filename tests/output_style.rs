@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod output_style_tests {
+    use std::io::ErrorKind;
+
+    use owo_colors::{AnsiColors, DynColors, XtermColors};
+
+    use okolib::output_style::{OutputStyle, TextStyle};
+
+    #[test]
+    fn test_parse_name() {
+        let style = TextStyle::parse("green").unwrap();
+
+        assert_eq!(style, TextStyle::from(AnsiColors::Green));
+    }
+
+    #[test]
+    fn test_parse_bright_name() {
+        let style = TextStyle::parse("bright red").unwrap();
+
+        assert_eq!(style, TextStyle::from(AnsiColors::BrightRed));
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        let style = TextStyle::parse("#ff8800").unwrap();
+
+        assert_eq!(style.color, Some(DynColors::Rgb(255, 136, 0)));
+    }
+
+    #[test]
+    fn test_parse_short_hex() {
+        let style = TextStyle::parse("#f80").unwrap();
+
+        assert_eq!(style.color, Some(DynColors::Rgb(255, 136, 0)));
+    }
+
+    #[test]
+    fn test_parse_rgb() {
+        let style = TextStyle::parse("rgb(255,136,0)").unwrap();
+
+        assert_eq!(style.color, Some(DynColors::Rgb(255, 136, 0)));
+    }
+
+    #[test]
+    fn test_parse_palette() {
+        let style = TextStyle::parse("208").unwrap();
+
+        assert_eq!(style.color, Some(DynColors::Xterm(XtermColors::from(208))));
+    }
+
+    #[test]
+    fn test_parse_attributes_and_background() {
+        let style = TextStyle::parse("bold underline yellow on blue").unwrap();
+
+        assert_eq!(style.color, Some(DynColors::Ansi(AnsiColors::Yellow)));
+        assert_eq!(style.background, Some(DynColors::Ansi(AnsiColors::Blue)));
+        assert!(style.bold);
+        assert!(style.underline);
+        assert!(!style.italic);
+    }
+
+    #[test]
+    fn test_parse_unknown_color() {
+        let error = TextStyle::parse("bold greenish").unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), "Unknown color greenish");
+    }
+
+    #[test]
+    fn test_parse_missing_background() {
+        let error = TextStyle::parse("red on").unwrap_err();
+
+        assert_eq!(error.to_string(), "Invalid style red on");
+    }
+
+    #[test]
+    fn test_paint_color() {
+        let style = TextStyle::parse("green").unwrap();
+
+        assert_eq!(style.paint("here"), "\u{1b}[32mhere\u{1b}[39m");
+    }
+
+    #[test]
+    fn test_paint_truecolor_bold() {
+        let style = TextStyle::parse("bold #ff8800").unwrap();
+
+        assert_eq!(style.paint("here"), "\u{1b}[38;2;255;136;0;1mhere\u{1b}[0m");
+    }
+
+    #[test]
+    fn test_css() {
+        let style = TextStyle::parse("underline 196 on #000").unwrap();
+
+        assert_eq!(
+            style.css(),
+            "color:#ff0000;background:#000000;text-decoration:underline"
+        );
+    }
+
+    #[test]
+    fn test_group_style_differs_from_pattern_style() {
+        let mut style = OutputStyle::new(false, None, Some(TextStyle::from(AnsiColors::Green)));
+        style.match_palette = ["yellow", "cyan", "magenta", "blue", "red"]
+            .iter()
+            .map(|color| TextStyle::parse(color).unwrap())
+            .collect();
+
+        for pattern in 0..10 {
+            for other_pattern in 0..10 {
+                for group in 1..10 {
+                    assert_ne!(
+                        style.get_group_style(pattern, group),
+                        style.get_match_style(other_pattern)
+                    );
+                }
+            }
+        }
+        assert_eq!(
+            style.get_group_style(0, 1).unwrap().color,
+            Some(DynColors::Ansi(AnsiColors::Yellow))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod records_tests {
+    use std::io::BufReader;
+
+    use regex::Regex;
+
+    use okolib::records::{read_records, RecordSeparator};
+
+    #[test]
+    fn test_read_records_start() {
+        let data = "intro\r\n# one\r\nbody\r\n# two\r\n".as_bytes();
+        let mut reader = BufReader::new(data);
+        let separator = RecordSeparator::Start(Regex::new("^# ").unwrap());
+
+        let records = read_records(&mut reader, &separator).unwrap();
+
+        let records: Vec<(usize, &str)> = records
+            .iter()
+            .map(|r| (r.line, r.content.as_str()))
+            .collect();
+        assert_eq!(
+            records,
+            vec![(1, "intro"), (2, "# one\r\nbody"), (4, "# two")]
+        );
+    }
+
+    #[test]
+    fn test_read_records_blank() {
+        let data = "\n\na\nb\n\n\t\nc\n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let records = read_records(&mut reader, &RecordSeparator::parse("blank")).unwrap();
+
+        let records: Vec<(usize, &str)> = records
+            .iter()
+            .map(|r| (r.line, r.content.as_str()))
+            .collect();
+        assert_eq!(records, vec![(3, "a\nb"), (7, "c")]);
+    }
+
+    #[test]
+    fn test_read_records_text() {
+        let data = "a;;b\nc;;;;d\n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let records = read_records(&mut reader, &RecordSeparator::parse(";;")).unwrap();
+
+        let records: Vec<(usize, &str)> = records
+            .iter()
+            .map(|r| (r.line, r.content.as_str()))
+            .collect();
+        assert_eq!(records, vec![(1, "a"), (1, "b\nc"), (2, "d")]);
+    }
+
+    #[test]
+    fn test_parse_record_separator() {
+        assert!(matches!(
+            RecordSeparator::parse("nul"),
+            RecordSeparator::Text(text) if text == "\0"
+        ));
+        assert!(matches!(
+            RecordSeparator::parse(r"\n--\n"),
+            RecordSeparator::Text(text) if text == "\n--\n"
+        ));
+    }
+}
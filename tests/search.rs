@@ -5,8 +5,14 @@ mod content;
 mod search_tests {
     use std::io::BufReader;
 
-    use okolib::search::{count, search};
-    use okolib::search_results::{Match, SearchResult, SearchResults};
+    use regex::Regex;
+
+    use okolib::records::RecordSeparator;
+    use okolib::search::{
+        count, count_breakdown, count_patterns, count_records, replace, replace_bytes, search,
+        search_bytes, search_multiline, search_patterns, search_records,
+    };
+    use okolib::search_results::{Group, Location, Match, SearchResult, SearchResults};
 
     use super::content::content::CONTENT;
 
@@ -33,7 +39,7 @@ mod search_tests {
         expected_results.add_result(result);
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = search(&mut reader, pattern, &false).unwrap();
 
@@ -69,7 +75,7 @@ mod search_tests {
         expected_results.add_result(result);
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = search(&mut reader, pattern, &true).unwrap();
 
@@ -99,7 +105,7 @@ mod search_tests {
         expected_results.add_result(result);
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = search(&mut reader, pattern, &false).unwrap();
 
@@ -135,13 +141,101 @@ mod search_tests {
         expected_results.add_result(result);
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = search(&mut reader, pattern, &true).unwrap();
 
         assert_eq!(expected_results, results);
     }
 
+    #[test]
+    fn test_search_capture_groups() {
+        let pattern = r"(?<name>Ja\w+)(\.|,)";
+
+        let mut expected_results = SearchResults::new(pattern.to_string());
+        let mut result = SearchResult::new(1, "Hi! I'm Jane. What's your name?".to_string());
+        let mut m = Match::new("Jane.".to_string(), 8, 13);
+        m.add_group(Group::new(
+            1,
+            Some("name".to_string()),
+            "Jane".to_string(),
+            8,
+            12,
+        ));
+        m.add_group(Group::new(2, None, ".".to_string(), 12, 13));
+        result.add_match(m);
+        expected_results.add_result(result);
+        result = SearchResult::new(2, "Hi Jane, I'm Jack. Nice to meet you.".to_string());
+        m = Match::new("Jane,".to_string(), 3, 8);
+        m.add_group(Group::new(
+            1,
+            Some("name".to_string()),
+            "Jane".to_string(),
+            3,
+            7,
+        ));
+        m.add_group(Group::new(2, None, ",".to_string(), 7, 8));
+        result.add_match(m);
+        m = Match::new("Jack.".to_string(), 13, 18);
+        m.add_group(Group::new(
+            1,
+            Some("name".to_string()),
+            "Jack".to_string(),
+            13,
+            17,
+        ));
+        m.add_group(Group::new(2, None, ".".to_string(), 17, 18));
+        result.add_match(m);
+        expected_results.add_result(result);
+        result = SearchResult::new(
+            3,
+            "Nice to meet you too, Jack. What brings you here?".to_string(),
+        );
+        m = Match::new("Jack.".to_string(), 22, 27);
+        m.add_group(Group::new(
+            1,
+            Some("name".to_string()),
+            "Jack".to_string(),
+            22,
+            26,
+        ));
+        m.add_group(Group::new(2, None, ".".to_string(), 26, 27));
+        result.add_match(m);
+        expected_results.add_result(result);
+
+        let data = CONTENT.as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = search(&mut reader, pattern, &false).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_search_patterns() {
+        let patterns = vec![
+            "concert".to_string(),
+            "for a".to_string(),
+            "con".to_string(),
+        ];
+
+        let mut expected_results = SearchResults::new("concert|for a|con".to_string());
+        expected_results.patterns = patterns.clone();
+        let mut result = SearchResult::new(5, "I'm here for a concert.".to_string());
+        let mut m = Match::new("for a".to_string(), 9, 14);
+        m.pattern = 1;
+        result.add_match(m);
+        result.add_match(Match::new("concert".to_string(), 15, 22));
+        expected_results.add_result(result);
+
+        let data = CONTENT.as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = search_patterns(&mut reader, &patterns, &false, &false).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
     #[test]
     fn test_count() {
         let pattern = "here";
@@ -149,7 +243,7 @@ mod search_tests {
         let expected_results = 4;
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = count(&mut reader, pattern, &false).unwrap();
 
@@ -163,7 +257,7 @@ mod search_tests {
         let expected_results = 5;
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = count(&mut reader, pattern, &true).unwrap();
 
@@ -177,7 +271,7 @@ mod search_tests {
         let expected_results = 4;
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = count(&mut reader, pattern, &false).unwrap();
 
@@ -191,10 +285,425 @@ mod search_tests {
         let expected_results = 5;
 
         let data = CONTENT.as_bytes();
-        let mut reader = BufReader::new(&data[..]);
+        let mut reader = BufReader::new(data);
 
         let results = count(&mut reader, pattern, &true).unwrap();
 
         assert_eq!(expected_results, results);
     }
+
+    #[test]
+    fn test_count_patterns() {
+        let patterns = vec!["here".to_string(), "Jack".to_string(), "ere".to_string()];
+
+        let expected_results = 7;
+
+        let data = CONTENT.as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = count_patterns(&mut reader, &patterns, &false).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_replace() {
+        let expected_results = "I'm there for a concert.\nThat's great.";
+
+        let data = "I'm here for a concert.\nThat's great.".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = replace(&mut reader, r"\bhere\b", "there", &false).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_replace_capture_groups() {
+        let expected_results =
+            "Hi! I'm Jane Doe. What's your name?\r\nHi Jane Doe, I'm Jack Doe.\r\n";
+
+        let data = "Hi! I'm Jane. What's your name?\r\nHi jane, I'm Jack.\r\n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = replace(&mut reader, r"(?<first>ja)(\w+)", "Ja$2 Doe", &true).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_replace_bytes_keeps_invalid_utf8() {
+        let expected_results = b"caf\xe9 there\r\n$\xff\n".to_vec();
+
+        let data = b"caf\xe9 here\r\n$\xff\n";
+
+        let results = replace_bytes(data, r"\bhere$", "there", &false);
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_replace_named_group() {
+        let expected_results = "key=user value=alice\n";
+
+        let data = "user=alice\n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = replace(
+            &mut reader,
+            r"(?<key>\w+)=(?<value>\w+)",
+            "key=${key} value=${value}",
+            &false,
+        )
+        .unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_search_extract_group() {
+        let data = "user=alice\nnothing here\nid=42\n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let mut results = search(&mut reader, r"(?<key>\w+)=(?<value>\w+)", &false).unwrap();
+        results.extract(Some("value"));
+
+        let extracted: Vec<&Vec<String>> = results.results.iter().map(|r| &r.extracted).collect();
+        assert_eq!(
+            extracted,
+            [&vec!["alice".to_string()], &vec!["42".to_string()]]
+        );
+
+        results.extract(Some("1"));
+        let extracted: Vec<&Vec<String>> = results.results.iter().map(|r| &r.extracted).collect();
+        assert_eq!(
+            extracted,
+            [&vec!["user".to_string()], &vec!["id".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_search_extract_unknown_group() {
+        let data = "user=alice\n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let mut results = search(&mut reader, r"(\w+)=(\w+)", &false).unwrap();
+        results.extract(Some("value"));
+
+        assert!(results.results.is_empty());
+    }
+
+    #[test]
+    fn test_search_patterns_overlapping() {
+        let mut expected_results = SearchResults::new("aa|a{3}".to_string());
+        expected_results.patterns = vec!["aa".to_string(), "a{3}".to_string()];
+        let mut result = SearchResult::new(1, "aaaa".to_string());
+        result.add_match(Match::new("aa".to_string(), 0, 2));
+        let mut m = Match::new("aaa".to_string(), 0, 3);
+        m.pattern = 1;
+        result.add_match(m);
+        result.add_match(Match::new("aa".to_string(), 1, 3));
+        let mut m = Match::new("aaa".to_string(), 1, 4);
+        m.pattern = 1;
+        result.add_match(m);
+        result.add_match(Match::new("aa".to_string(), 2, 4));
+        expected_results.add_result(result);
+
+        let data = "aaaa".as_bytes();
+        let mut reader = BufReader::new(data);
+        let patterns = vec!["aa".to_string(), "a{3}".to_string()];
+
+        let results = search_patterns(&mut reader, &patterns, &false, &true).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_count_breakdown_overlapping() {
+        let data = "aaaa\nbaab\néaaé\n".as_bytes();
+
+        let mut reader = BufReader::new(data);
+        let patterns = vec!["aa".to_string()];
+        let results = count_breakdown(&mut reader, &patterns, &false, &false, &false).unwrap();
+        assert_eq!(results.total, 4);
+
+        let mut reader = BufReader::new(data);
+        let results = count_breakdown(&mut reader, &patterns, &false, &true, &false).unwrap();
+        assert_eq!(results.total, 5);
+        assert_eq!(results.lines, 3);
+    }
+
+    #[test]
+    fn test_search_multiline() {
+        let mut expected_results = SearchResults::new(r"Error.*?\n\s+at \S+".to_string());
+        let mut result = SearchResult::new(2, "Error: boom\r\n  at a.rs:1".to_string());
+        let mut m = Match::new("Error: boom\r\n  at a.rs:1".to_string(), 0, 24);
+        m.location = Some(Location::new(2, 1, 3, 12));
+        result.add_match(m);
+        expected_results.add_result(result);
+        let mut result = SearchResult::new(5, "Ünïcode error: x\n  at b.rs:2".to_string());
+        let mut m = Match::new("error: x\n  at b.rs:2".to_string(), 10, 30);
+        m.location = Some(Location::new(5, 9, 6, 12));
+        result.add_match(m);
+        expected_results.add_result(result);
+
+        let data =
+            "start\nError: boom\r\n  at a.rs:1\nok\nÜnïcode error: x\n  at b.rs:2\n".as_bytes();
+        let mut reader = BufReader::new(data);
+        let patterns = vec![r"Error.*?\n\s+at \S+".to_string()];
+
+        let results = search_multiline(&mut reader, &patterns, &true, &false).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_search_multiline_joins_matches_on_common_lines() {
+        let data = "a1\nb2\na3\nc4\n".as_bytes();
+        let mut reader = BufReader::new(data);
+        let patterns = vec![r"a\d\nb".to_string(), r"2\na".to_string()];
+
+        let results = search_multiline(&mut reader, &patterns, &false, &true).unwrap();
+
+        assert_eq!(results.results.len(), 1);
+        assert_eq!(results.results[0].line, 1);
+        assert_eq!(results.results[0].content, "a1\nb2\na3");
+        assert_eq!(results.results[0].matches.len(), 2);
+    }
+
+    #[test]
+    fn test_search_multiline_final_new_line() {
+        let patterns = vec!["^$".to_string()];
+        let results = search_multiline(
+            &mut BufReader::new("x\n\n".as_bytes()),
+            &patterns,
+            &false,
+            &false,
+        )
+        .unwrap();
+        let lines: Vec<usize> = results.results.iter().map(|result| result.line).collect();
+        assert_eq!(lines, vec![2]);
+
+        let patterns = vec!["$".to_string()];
+        let results = search_multiline(
+            &mut BufReader::new("x\ny\n".as_bytes()),
+            &patterns,
+            &false,
+            &false,
+        )
+        .unwrap();
+        let lines: Vec<usize> = results.results.iter().map(|result| result.line).collect();
+        assert_eq!(lines, vec![1, 2]);
+
+        // Without final new line, end of the last line still matches
+        let results = search_multiline(
+            &mut BufReader::new("x\ny".as_bytes()),
+            &patterns,
+            &false,
+            &false,
+        )
+        .unwrap();
+        let lines: Vec<usize> = results.results.iter().map(|result| result.line).collect();
+        assert_eq!(lines, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_search_multiline_match_ending_with_new_line() {
+        let mut expected_results = SearchResults::new(r"d\r?\n(e)\r?\n".to_string());
+        let mut result = SearchResult::new(4, "d\r\ne".to_string());
+        let mut m = Match::new("d\r\ne".to_string(), 0, 4);
+        m.add_group(Group::new(1, None, "e".to_string(), 3, 4));
+        m.location = Some(Location::new(4, 1, 5, 2));
+        result.add_match(m);
+        expected_results.add_result(result);
+
+        let patterns = vec![r"d\r?\n(e)\r?\n".to_string()];
+        let results = search_multiline(
+            &mut BufReader::new("a\nb\nc\nd\r\ne\r\n".as_bytes()),
+            &patterns,
+            &false,
+            &false,
+        )
+        .unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_search_records() {
+        let mut expected_results = SearchResults::new(r"at \w+".to_string());
+        let mut result =
+            SearchResult::new(2, "2024-01-01 ERROR boom\n  at foo\n  at bar".to_string());
+        let mut m = Match::new("at foo".to_string(), 24, 30);
+        m.location = Some(Location::new(3, 3, 3, 9));
+        result.add_match(m);
+        let mut m = Match::new("at bar".to_string(), 33, 39);
+        m.location = Some(Location::new(4, 3, 4, 9));
+        result.add_match(m);
+        expected_results.add_result(result);
+
+        let data = "2024-01-01 INFO start\n2024-01-01 ERROR boom\n  at foo\n  at bar\n2024-01-02 INFO ok\n"
+            .as_bytes();
+        let mut reader = BufReader::new(data);
+        let patterns = vec![r"at \w+".to_string()];
+        let separator = RecordSeparator::Start(Regex::new(r"^\d{4}-").unwrap());
+
+        let results = search_records(&mut reader, &patterns, &false, &false, &separator).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_search_records_blank_separator() {
+        let data = "a b\nc\n\n \n\nd\ne c\n".as_bytes();
+        let mut reader = BufReader::new(data);
+        let patterns = vec!["c".to_string()];
+
+        let results = search_records(
+            &mut reader,
+            &patterns,
+            &false,
+            &false,
+            &RecordSeparator::parse("blank"),
+        )
+        .unwrap();
+
+        assert_eq!(results.results.len(), 2);
+        assert_eq!(results.results[0].line, 1);
+        assert_eq!(results.results[0].content, "a b\nc");
+        assert_eq!(results.results[1].line, 6);
+        assert_eq!(results.results[1].content, "d\ne c");
+    }
+
+    #[test]
+    fn test_count_records() {
+        let data = "x1\0y\ny2\0x3 x4".as_bytes();
+        let mut reader = BufReader::new(data);
+        let patterns = vec![r"x\d".to_string(), r"y\d".to_string()];
+
+        let results = count_records(
+            &mut reader,
+            &patterns,
+            &false,
+            &false,
+            &true,
+            &RecordSeparator::parse("nul"),
+        )
+        .unwrap();
+
+        assert_eq!(results.total, 4);
+        assert_eq!(results.lines, 3);
+        assert_eq!(results.patterns[0].total, 3);
+        assert_eq!(results.patterns[1].total, 1);
+        assert_eq!(results.line_counts[1].line, 1);
+        assert_eq!(results.line_counts[2].line, 2);
+        assert_eq!(results.line_counts[2].total, 2);
+    }
+
+    #[test]
+    fn test_search_bytes() {
+        let content = "ERROR one\r\nok\nerror: two\n\ncaf\u{e9} \u{fc}\nok ERROR\nlast error";
+        for (pattern, ignore_case) in [
+            ("error", &true),
+            ("^ERROR", &false),
+            ("or$", &false),
+            (r"\s+ok", &false),
+            ("^$", &false),
+            (r"(?<first>\w)\w+ (\w+)", &false),
+            ("ü", &false),
+            (r"\Aok\z", &false),
+            (r"(?-m)^last error$", &false),
+            (r"\Aerror: two\z", &true),
+        ] {
+            let patterns = vec![pattern.to_string(), "ok".to_string()];
+            let mut reader = BufReader::new(content.as_bytes());
+            let expected_results =
+                search_patterns(&mut reader, &patterns, ignore_case, &false).unwrap();
+
+            let results = search_bytes(content.as_bytes(), &patterns, ignore_case, &false);
+
+            assert_eq!(expected_results, results, "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_search_bytes_line_boundaries() {
+        for content in ["", "\n", "a\n\nb", "x\ny", "x\ny\n", "a\rb\r\nb\r\n\r\n"] {
+            for pattern in ["^$", "$", "^", "b$", r"a\s*b", "a.b", r"\Ab\z", "x|y"] {
+                let patterns = vec![pattern.to_string()];
+                let mut reader = BufReader::new(content.as_bytes());
+                let expected_results =
+                    search_patterns(&mut reader, &patterns, &false, &false).unwrap();
+
+                let results = search_bytes(content.as_bytes(), &patterns, &false, &false);
+
+                assert_eq!(expected_results, results, "{:?} in {:?}", pattern, content);
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_bytes_invalid_utf8() {
+        let content = b"caf\xe9 here\n\xff\nthere\n";
+        let patterns = vec!["here".to_string()];
+
+        let results = search_bytes(content, &patterns, &false, &false);
+
+        assert_eq!(results.results.len(), 2);
+        assert_eq!(results.results[0].line, 1);
+        assert_eq!(results.results[0].content, "caf\u{FFFD} here");
+        assert_eq!(results.results[1].line, 3);
+    }
+
+    #[test]
+    fn test_count_breakdown_same_as_search() {
+        let content = "ERROR one\r\naaaa ok\néaaé\n\ncaf\u{e9} \u{fc}\nok ERROR\nlast error";
+        for (patterns, ignore_case, overlapping) in [
+            (vec!["error"], &true, &false),
+            (vec!["aa"], &false, &true),
+            (vec!["aa", "a", "ok"], &false, &false),
+            (vec!["aa", "a", "ok"], &false, &true),
+            (vec![r"(\w)\w+", "é"], &true, &false),
+            (vec!["", "ü"], &false, &false),
+            (vec!["^$"], &false, &false),
+        ] {
+            let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+            let mut reader = BufReader::new(content.as_bytes());
+            let search_results =
+                search_patterns(&mut reader, &patterns, ignore_case, overlapping).unwrap();
+
+            let mut reader = BufReader::new(content.as_bytes());
+            let results =
+                count_breakdown(&mut reader, &patterns, ignore_case, overlapping, &true).unwrap();
+
+            let line_counts: Vec<(usize, usize)> = search_results
+                .results
+                .iter()
+                .map(|result| (result.line, result.matches.len()))
+                .collect();
+            assert_eq!(
+                line_counts,
+                results
+                    .line_counts
+                    .iter()
+                    .map(|line_count| (line_count.line, line_count.total))
+                    .collect::<Vec<(usize, usize)>>(),
+                "{:?}",
+                patterns
+            );
+        }
+    }
+
+    #[test]
+    fn test_search_patterns_invalid_utf8() {
+        let content = b"caf\xe9 here\n\xff\nthere\n";
+        let patterns = vec!["here".to_string()];
+
+        let mut reader = BufReader::new(&content[..]);
+        let results = search_patterns(&mut reader, &patterns, &false, &false).unwrap();
+
+        assert_eq!(results.results.len(), 2);
+        assert_eq!(results.results[0].content, "caf\u{FFFD} here");
+        assert_eq!(results.results[1].line, 3);
+    }
 }
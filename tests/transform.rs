@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod transform_tests {
+    use std::io::BufReader;
+    use std::str::FromStr;
+
+    use okolib::transform::{
+        nfc, nfkc, pad, pad_left, reverse, slugify, title, transform, transform_lines, trim,
+        Operation,
+    };
+
+    #[test]
+    fn test_operation_from_str() {
+        assert_eq!(Operation::from_str("upper").unwrap(), Operation::Upper);
+        assert_eq!(Operation::from_str("nfkc").unwrap(), Operation::Nfkc);
+        assert_eq!(Operation::from_str("pad:8").unwrap(), Operation::Pad(8));
+        assert_eq!(
+            Operation::from_str("pad-left:3").unwrap(),
+            Operation::PadLeft(3)
+        );
+        assert!(Operation::from_str("pad").is_err());
+        assert!(Operation::from_str("pad:x").is_err());
+        assert!(Operation::from_str("upper:2").is_err());
+        assert!(Operation::from_str("shout").is_err());
+    }
+
+    #[test]
+    fn test_operations() {
+        assert_eq!(title("hI! i'm jane doe."), "Hi! I'm Jane Doe.");
+        assert_eq!(trim("  Jane \t"), "Jane");
+        assert_eq!(slugify("  Crème Brûlée, please!  "), "creme-brulee-please");
+        assert_eq!(nfc("e\u{301}"), "\u{e9}");
+        assert_eq!(nfkc("ﬁ²"), "fi2");
+        assert_eq!(reverse("Jane\u{301}"), "e\u{301}naJ");
+        assert_eq!(pad("Jane", 6), "Jane  ");
+        assert_eq!(pad_left("Jane", 6), "  Jane");
+        assert_eq!(pad("Jane", 2), "Jane");
+    }
+
+    #[test]
+    fn test_transform_chain() {
+        let operations = [Operation::Trim, Operation::Upper, Operation::PadLeft(6)];
+
+        assert_eq!(transform("  jane ", &operations), "  JANE");
+    }
+
+    #[test]
+    fn test_transform_lines() {
+        let expected_results = "Hi! I'm JANE.\r\nHi JANE, I'm JACK.";
+
+        let data = "Hi! I'm Jane.\r\nHi jane, I'm Jack.".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results =
+            transform_lines(&mut reader, Some(r"ja\w+"), &true, &[Operation::Upper]).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+
+    #[test]
+    fn test_transform_whole_lines() {
+        let expected_results = "hi-i-m-jane\nhi-jane\n";
+
+        let data = "Hi! I'm Jane.\n Hi Jane \n".as_bytes();
+        let mut reader = BufReader::new(data);
+
+        let results = transform_lines(&mut reader, None, &false, &[Operation::Slugify]).unwrap();
+
+        assert_eq!(expected_results, results);
+    }
+}
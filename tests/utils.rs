@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 pub mod utils {
     pub struct TestContext<F: Fn()> {
         pub teardown_fn: F,
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod watch_tests {
+    use std::fs::{remove_file, write};
+
+    use assert_fs::TempDir;
+
+    use okolib::count_results::{CountDelta, CountResults, PatternCount};
+    use okolib::watch::Watcher;
+
+    fn get_results(files: &[(&str, usize)], pattern_totals: &[usize]) -> CountResults {
+        let mut results = CountResults::new("ERROR|WARN".to_string());
+        results.patterns = ["ERROR", "WARN"]
+            .iter()
+            .map(|pattern| PatternCount::new(pattern.to_string(), 0))
+            .collect();
+        for (path, total) in files {
+            let mut file_results = CountResults::new("ERROR|WARN".to_string());
+            file_results.total = *total;
+            results.add_file(path.to_string(), file_results);
+        }
+        for (pattern, total) in results.patterns.iter_mut().zip(pattern_totals) {
+            pattern.total = *total;
+        }
+        results
+    }
+
+    #[test]
+    fn test_watcher_has_changed() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.log");
+        write(&path, "one\n").unwrap();
+
+        let mut watcher = Watcher::new(std::slice::from_ref(&path));
+        assert!(!watcher.has_changed());
+
+        write(&path, "one\ntwo\n").unwrap();
+        assert!(watcher.has_changed());
+        assert!(!watcher.has_changed());
+
+        remove_file(&path).unwrap();
+        assert!(watcher.has_changed());
+        assert!(!watcher.has_changed());
+    }
+
+    #[test]
+    fn test_count_delta() {
+        let previous = get_results(&[("a.log", 3), ("b.log", 2)], &[4, 1]);
+        let current = get_results(&[("a.log", 3), ("c.log", 1)], &[2, 2]);
+
+        let delta = CountDelta::new(&previous, &current);
+
+        assert_eq!(
+            delta,
+            CountDelta {
+                total: -1,
+                files: vec![("c.log".to_string(), 1), ("b.log".to_string(), -2)],
+                patterns: vec![("ERROR".to_string(), -2), ("WARN".to_string(), 1)],
+            }
+        );
+    }
+}